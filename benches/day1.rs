@@ -0,0 +1,40 @@
+use aoc_rust_2024::day1::{
+    input_generator, part1, part1_streaming, part2, part2_streaming, synthetic_input, SortedLists,
+};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use std::hint::black_box;
+
+const SIZES: [usize; 3] = [1_000, 100_000, 1_000_000];
+
+/// Parsing and solving together, since the streaming mode does its sorting while parsing.
+fn scaling(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day1");
+    group.sample_size(10);
+
+    for pairs in SIZES {
+        let input = synthetic_input(pairs, 2024);
+        group.throughput(Throughput::Elements(pairs as u64));
+
+        group.bench_with_input(
+            BenchmarkId::new("nom_hashing", pairs),
+            &input,
+            |b, input| {
+                b.iter(|| {
+                    let lists = input_generator(input.trim_end()).unwrap();
+                    black_box((part1(&lists), part2(&lists)))
+                });
+            },
+        );
+        group.bench_with_input(BenchmarkId::new("streaming", pairs), &input, |b, input| {
+            b.iter(|| {
+                let lists = SortedLists::parse(input).unwrap();
+                black_box((part1_streaming(&lists), part2_streaming(&lists)))
+            });
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, scaling);
+criterion_main!(benches);
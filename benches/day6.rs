@@ -0,0 +1,46 @@
+use aoc_runner::ArcStr;
+use aoc_rust_2024::{utils::input::InputCache, Day6Part2, Day6Part2SIMULATED, Factory};
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+
+const SAMPLE: &str = "....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...";
+
+fn inputs() -> Vec<(&'static str, String)> {
+    let mut inputs = vec![("sample", SAMPLE.to_string())];
+
+    if let Ok(prod) = InputCache::from_env().with_session(None).load(6) {
+        inputs.push(("prod", prod.trim_end().to_string()));
+    }
+
+    inputs
+}
+
+fn part2(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day6_part2");
+
+    for (name, input) in inputs() {
+        let jump_table = Factory::day6_part2(ArcStr::from(&input)).unwrap();
+        let simulated = Factory::day6_part2_simulated(ArcStr::from(&input)).unwrap();
+
+        group.bench_function(format!("jump_table/{name}"), |b| {
+            b.iter(|| black_box(jump_table.run()));
+        });
+        group.bench_function(format!("simulated/{name}"), |b| {
+            b.iter(|| black_box(simulated.run()));
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, part2);
+criterion_main!(benches);
@@ -0,0 +1,65 @@
+use aoc_runner::ArcStr;
+use aoc_rust_2024::{
+    utils::input::InputCache, Day7Part1, Day7Part1FORWARD, Day7Part2, Day7Part2FORWARD, Factory,
+};
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+
+const SAMPLE: &str = "190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20";
+
+fn inputs() -> Vec<(&'static str, String)> {
+    let mut inputs = vec![("sample", SAMPLE.to_string())];
+
+    if let Ok(prod) = InputCache::from_env().with_session(None).load(7) {
+        inputs.push(("prod", prod.trim_end().to_string()));
+    }
+
+    inputs
+}
+
+fn part1(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day7_part1");
+
+    for (name, input) in inputs() {
+        let reverse = Factory::day7_part1(ArcStr::from(&input)).unwrap();
+        let forward = Factory::day7_part1_forward(ArcStr::from(&input)).unwrap();
+
+        group.bench_function(format!("reverse/{name}"), |b| {
+            b.iter(|| black_box(reverse.run()));
+        });
+        group.bench_function(format!("forward/{name}"), |b| {
+            b.iter(|| black_box(forward.run()));
+        });
+    }
+
+    group.finish();
+}
+
+fn part2(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day7_part2");
+
+    for (name, input) in inputs() {
+        let reverse = Factory::day7_part2(ArcStr::from(&input)).unwrap();
+        let forward = Factory::day7_part2_forward(ArcStr::from(&input)).unwrap();
+
+        group.bench_function(format!("reverse/{name}"), |b| {
+            b.iter(|| black_box(reverse.run()));
+        });
+        group.bench_function(format!("forward/{name}"), |b| {
+            b.iter(|| black_box(forward.run()));
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, part1, part2);
+criterion_main!(benches);
@@ -0,0 +1,150 @@
+//! Every solution in the `SOLUTIONS` table, timing the generator and the solution separately on
+//! the sample and, when the input cache already has it, on the real input. `aoc bench-summary`
+//! reads the results back into a table and compares them with a baseline.
+
+use aoc_runner::ArcStr;
+use aoc_rust_2024::{
+    benchmarks::{function_id, group_id, Stage},
+    solutions::{Solution, SOLUTIONS},
+    utils::input::InputCache,
+};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use indoc::indoc;
+use itertools::Itertools;
+use std::hint::black_box;
+
+/// The puzzle's example for each day, and for day 3 part 2 its own second example.
+fn sample(solution: &Solution) -> Option<&'static str> {
+    let sample = match (solution.day, solution.part) {
+        (1, _) => indoc! {
+            "3   4
+             4   3
+             2   5
+             1   3
+             3   9
+             3   3"
+        },
+        (2, _) => indoc! {
+            "7 6 4 2 1
+             1 2 7 8 9
+             9 7 6 2 1
+             1 3 2 4 5
+             8 6 4 4 1
+             1 3 6 7 9"
+        },
+        (3, 1) => "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))",
+        (3, _) => "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))",
+        (4, _) => indoc! {
+            "MMMSXXMASM
+             MSAMXMSMSA
+             AMXSXMAAMM
+             MSAMASMSMX
+             XMASAMXAMM
+             XXAMMXXAMA
+             SMSMSASXSS
+             SAXAMASAAA
+             MAMMMXMMMM
+             MXMXAXMASX"
+        },
+        (5, _) => indoc! {
+            "47|53
+             97|13
+             97|61
+             97|47
+             75|29
+             61|13
+             75|53
+             29|13
+             97|29
+             53|29
+             61|53
+             97|53
+             61|29
+             47|13
+             75|47
+             97|75
+             47|61
+             75|61
+             47|29
+             75|13
+             53|13
+
+             75,47,61,53,29
+             97,61,53,29,13
+             75,29,13
+             75,97,47,61,53
+             61,13,29
+             97,13,75,29,47"
+        },
+        (6, _) => indoc! {
+            "....#.....
+             .........#
+             ..........
+             ..#.......
+             .......#..
+             ..........
+             .#..^.....
+             ........#.
+             #.........
+             ......#..."
+        },
+        (7, _) => indoc! {
+            "190: 10 19
+             3267: 81 40 27
+             83: 17 5
+             156: 15 6
+             7290: 6 8 6 15
+             161011: 16 10 13
+             192: 17 8 14
+             21037: 9 7 18 13
+             292: 11 6 16 20"
+        },
+        _ => return None,
+    };
+
+    Some(sample)
+}
+
+fn inputs(solution: &Solution, cache: &InputCache) -> Vec<(&'static str, ArcStr)> {
+    let mut inputs = Vec::new();
+
+    if let Some(sample) = sample(solution) {
+        inputs.push(("sample", ArcStr::from(sample)));
+    }
+    if let Ok(prod) = cache.load(solution.day) {
+        inputs.push(("prod", ArcStr::from(prod.trim_end())));
+    }
+
+    inputs
+}
+
+fn solutions(c: &mut Criterion) {
+    // Without a session the cache only reads what is already there, so benching never fetches.
+    let cache = InputCache::from_env().with_session(None);
+
+    for (day, solutions) in &SOLUTIONS.iter().chunk_by(|solution| solution.day) {
+        let mut group = c.benchmark_group(group_id(day));
+        group.sample_size(10);
+
+        for solution in solutions {
+            for (input_name, input) in inputs(solution, &cache) {
+                group.bench_with_input(
+                    BenchmarkId::new(function_id(solution, Stage::Generator), input_name),
+                    &input,
+                    |b, input| b.iter(|| black_box((solution.construct)(input.clone()).unwrap())),
+                );
+
+                let runner = (solution.construct)(input).unwrap();
+                group.bench_function(
+                    BenchmarkId::new(function_id(solution, Stage::Solve), input_name),
+                    |b| b.iter(|| black_box(runner.try_run().unwrap())),
+                );
+            }
+        }
+
+        group.finish();
+    }
+}
+
+criterion_group!(benches, solutions);
+criterion_main!(benches);
@@ -0,0 +1,770 @@
+//! Known answers, keyed by the input they belong to, and a harness that checks every solution
+//! against them.
+//!
+//! Answers live in `answers.toml`, one `[[answer]]` per year, day, part and input, where the input
+//! is identified by the SHA-256 of its text. That lets everyone's inputs sit side by side: the
+//! harness runs each solution over each input directory it is given, compares what comes out with
+//! the recorded answer for that input, and can record answers for inputs it has not seen before.
+//!
+//! An answer can also leave out its input, for one that was known before the input it belongs to
+//! was at hand. An input of its day that has no answer of its own and matches an unpinned answer
+//! passes, and `aoc verify --record` pins the answer to that input's hash. One that does not match
+//! may simply be someone else's input, so it is reported as new rather than failed, and recording
+//! gives it an answer of its own while the unpinned one waits for its input.
+
+use crate::{
+    solutions::{Solution, SOLUTIONS},
+    utils::input::{input_path, YEAR},
+};
+use aoc_runner::ArcStr;
+use color_eyre::eyre::{eyre, Result, WrapErr};
+use itertools::Itertools;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Write as _},
+    fs,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+};
+
+pub const ANSWERS_PATH: &str = "answers.toml";
+
+const ANSWERS_HEADER: &str =
+    "# Answers checked by `aoc verify`, keyed by the SHA-256 of the input they belong to.\n\
+     # Add answers for new inputs with `aoc verify --record` rather than by hand. An answer\n\
+     # without an input belongs to an input not seen yet; `--record` pins it to one that matches.\n";
+
+/// How many characters of an input's hash to show in reports.
+const SHORT_HASH: usize = 12;
+
+/// Identifies an input by the SHA-256 of its text, ignoring trailing whitespace the way `cargo aoc`
+/// does.
+#[must_use]
+pub fn input_hash(input: &str) -> String {
+    Sha256::digest(input.trim_end().as_bytes())
+        .iter()
+        .fold(String::new(), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AnswerKey {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub input: String,
+}
+
+impl fmt::Display for AnswerKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} day {} part {} for input {}",
+            self.year,
+            self.day,
+            self.part,
+            short(&self.input)
+        )
+    }
+}
+
+/// The year, day and part of an answer that is not pinned to an input yet.
+type Unpinned = (u32, u32, u32);
+
+/// One entry of the answers file.
+#[derive(Debug, Serialize, Deserialize)]
+struct Record {
+    year: u32,
+    day: u32,
+    part: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    input: Option<String>,
+    answer: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct AnswersFile {
+    #[serde(default, rename = "answer")]
+    answers: Vec<Record>,
+}
+
+/// The contents of an answers file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    answers: BTreeMap<AnswerKey, String>,
+    unpinned: BTreeMap<Unpinned, String>,
+}
+
+impl Answers {
+    /// Reads the answers at `path`, which may not exist yet.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the file exists but cannot be read, or is not a valid answers file.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let text = fs::read_to_string(path)
+            .wrap_err_with(|| format!("could not read {}", path.display()))?;
+        Self::parse(&text)
+            .wrap_err_with(|| format!("{} is not a valid answers file", path.display()))
+    }
+
+    /// # Errors
+    ///
+    /// Fails if `text` is not a valid answers file, including when it gives two different answers
+    /// for the same key.
+    pub fn parse(text: &str) -> Result<Self> {
+        let file: AnswersFile = toml::from_str(text)?;
+        let mut answers = Self::default();
+
+        for record in file.answers {
+            let Some(input) = record.input else {
+                let unpinned = (record.year, record.day, record.part);
+                if let Some(existing) = answers.unpinned.get(&unpinned) {
+                    if *existing != record.answer {
+                        return Err(eyre!(
+                            "{} day {} part {} is recorded as both {existing} and {}",
+                            record.year,
+                            record.day,
+                            record.part,
+                            record.answer
+                        ));
+                    }
+                }
+
+                answers.unpinned.insert(unpinned, record.answer);
+                continue;
+            };
+
+            let key = AnswerKey {
+                year: record.year,
+                day: record.day,
+                part: record.part,
+                input,
+            };
+            if let Some(existing) = answers.get(&key) {
+                if existing != record.answer {
+                    return Err(eyre!(
+                        "{key} is recorded as both {existing} and {}",
+                        record.answer
+                    ));
+                }
+            }
+
+            answers.insert(key, record.answer);
+        }
+
+        Ok(answers)
+    }
+
+    /// # Errors
+    ///
+    /// Fails if the file cannot be written.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, self.to_string())
+            .wrap_err_with(|| format!("could not write {}", path.display()))
+    }
+
+    /// The answer recorded for exactly this input.
+    #[must_use]
+    pub fn get(&self, key: &AnswerKey) -> Option<&str> {
+        self.answers.get(key).map(String::as_str)
+    }
+
+    /// The answer for `key`'s part that is not pinned to any input.
+    #[must_use]
+    pub fn unpinned(&self, key: &AnswerKey) -> Option<&str> {
+        self.unpinned
+            .get(&(key.year, key.day, key.part))
+            .map(String::as_str)
+    }
+
+    /// Records `answer` for the input in `key`. Any unpinned answer for its part is left alone,
+    /// since it may belong to a different input.
+    pub fn insert(&mut self, key: AnswerKey, answer: String) {
+        self.answers.insert(key, answer);
+    }
+
+    /// Pins the unpinned answer for `key`'s part to the input in `key`.
+    fn pin(&mut self, key: &AnswerKey) {
+        if let Some(answer) = self.unpinned.remove(&(key.year, key.day, key.part)) {
+            self.answers.insert(key.clone(), answer);
+        }
+    }
+
+    /// Records `answer` for a part without saying which input it belongs to.
+    pub fn insert_unpinned(&mut self, year: u32, day: u32, part: u32, answer: String) {
+        self.unpinned.insert((year, day, part), answer);
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.answers.len() + self.unpinned.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.answers.is_empty() && self.unpinned.is_empty()
+    }
+}
+
+/// Renders the answers as an answers file, sorted by key so that diffs stay small.
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let file = AnswersFile {
+            answers: self
+                .answers
+                .iter()
+                .map(|(key, answer)| Record {
+                    year: key.year,
+                    day: key.day,
+                    part: key.part,
+                    input: Some(key.input.clone()),
+                    answer: answer.clone(),
+                })
+                .chain(
+                    self.unpinned
+                        .iter()
+                        .map(|(&(year, day, part), answer)| Record {
+                            year,
+                            day,
+                            part,
+                            input: None,
+                            answer: answer.clone(),
+                        }),
+                )
+                .collect(),
+        };
+
+        writeln!(f, "{ANSWERS_HEADER}")?;
+        f.write_str(&toml::to_string(&file).map_err(|_| fmt::Error)?)
+    }
+}
+
+/// A directory of inputs laid out like the input cache, named for the report.
+#[derive(Debug, Clone)]
+pub struct InputSet {
+    pub label: String,
+    pub dir: PathBuf,
+}
+
+impl InputSet {
+    pub fn new(label: impl Into<String>, dir: impl Into<PathBuf>) -> Self {
+        Self {
+            label: label.into(),
+            dir: dir.into(),
+        }
+    }
+
+    /// A set labelled with its own path.
+    pub fn from_dir(dir: impl Into<PathBuf>) -> Self {
+        let dir = dir.into();
+        Self::new(dir.display().to_string(), dir)
+    }
+}
+
+/// One input found in an [`InputSet`].
+#[derive(Debug, Clone)]
+struct Input {
+    label: String,
+    day: u32,
+    hash: String,
+    text: ArcStr,
+}
+
+/// Reads whichever days' inputs each set has; missing days are not an error.
+fn gather(sets: &[InputSet]) -> Result<Vec<Input>> {
+    let days = SOLUTIONS.iter().map(|s| s.day).unique().collect_vec();
+    let mut inputs = Vec::new();
+
+    for set in sets {
+        for &day in &days {
+            let path = input_path(&set.dir, YEAR, day);
+            if !path.exists() {
+                continue;
+            }
+
+            let text = fs::read_to_string(&path)
+                .wrap_err_with(|| format!("could not read {}", path.display()))?;
+            inputs.push(Input {
+                label: set.label.clone(),
+                day,
+                hash: input_hash(&text),
+                text: ArcStr::from(text.trim_end()),
+            });
+        }
+    }
+
+    Ok(inputs)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    /// The answer matches the recorded one.
+    Pass,
+    /// The answer differs from the recorded one.
+    Fail { expected: String },
+    /// There is no recorded answer for this input.
+    New,
+    /// There is no recorded answer for this input, and the answer differs from the unpinned one
+    /// for its part, which may well belong to another input.
+    Unpinned { unpinned: String },
+    /// The generator or the solution returned an error.
+    Error(String),
+}
+
+impl Status {
+    /// Whether the input has no answer of its own yet, so `--record` may add one.
+    #[must_use]
+    pub fn is_new(&self) -> bool {
+        matches!(self, Status::New | Status::Unpinned { .. })
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Pass => write!(f, "pass"),
+            Status::Fail { expected } => write!(f, "FAIL, expected {expected}"),
+            Status::New => write!(f, "new"),
+            Status::Unpinned { unpinned } => write!(f, "new, unpinned answer is {unpinned}"),
+            Status::Error(message) => write!(f, "ERROR: {message}"),
+        }
+    }
+}
+
+/// The result of running one solution on one input.
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    pub label: String,
+    pub solution: &'static Solution,
+    pub key: AnswerKey,
+    pub answer: Option<String>,
+    pub status: Status,
+}
+
+/// Every outcome of a verification run, in input order, then solution order.
+#[derive(Debug, Clone)]
+pub struct Verification {
+    pub outcomes: Vec<Outcome>,
+}
+
+/// Runs every solution on every input the sets have, in parallel, checking each answer against
+/// `answers`.
+///
+/// # Errors
+///
+/// Fails only if an input cannot be read; solutions that fail are reported as such.
+pub fn verify(sets: &[InputSet], answers: &Answers) -> Result<Verification> {
+    let inputs = gather(sets)?;
+    let jobs = inputs
+        .iter()
+        .flat_map(|input| {
+            SOLUTIONS
+                .iter()
+                .filter(move |solution| solution.day == input.day)
+                .map(move |solution| (input, solution))
+        })
+        .collect_vec();
+
+    let outcomes = jobs
+        .into_par_iter()
+        .map(|(input, solution)| {
+            let key = AnswerKey {
+                year: YEAR,
+                day: input.day,
+                part: solution.part,
+                input: input.hash.clone(),
+            };
+
+            let (answer, status) = match solve(solution, input.text.clone()) {
+                Err(message) => (None, Status::Error(message)),
+                Ok(answer) => {
+                    let status = match (answers.get(&key), answers.unpinned(&key)) {
+                        (Some(expected), _) if expected == answer => Status::Pass,
+                        (Some(expected), _) => Status::Fail {
+                            expected: expected.to_string(),
+                        },
+                        (None, Some(unpinned)) if unpinned == answer => Status::Pass,
+                        (None, Some(unpinned)) => Status::Unpinned {
+                            unpinned: unpinned.to_string(),
+                        },
+                        (None, None) => Status::New,
+                    };
+                    (Some(answer), status)
+                }
+            };
+
+            Outcome {
+                label: input.label.clone(),
+                solution,
+                key,
+                answer,
+                status,
+            }
+        })
+        .collect();
+
+    Ok(Verification { outcomes })
+}
+
+/// Runs `solution` on `input`, turning a panic into an error so one unfinished day cannot stop
+/// the whole run.
+fn solve(solution: &Solution, input: ArcStr) -> Result<String, String> {
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        let runner = (solution.construct)(input).map_err(|e| format!("generator: {e}"))?;
+        let answer = runner.try_run().map_err(|e| e.to_string())?;
+        Ok(answer.to_string())
+    }))
+    .unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(ToString::to_string)
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "no message".to_string());
+        Err(format!("panicked: {message}"))
+    })
+}
+
+impl Verification {
+    /// Whether any solution failed or errored.
+    #[must_use]
+    pub fn failed(&self) -> bool {
+        self.outcomes
+            .iter()
+            .any(|outcome| matches!(outcome.status, Status::Fail { .. } | Status::Error(_)))
+    }
+
+    /// Records the answers to inputs that had none, returning the keys that were added. Inputs
+    /// that matched an unpinned answer get it pinned to them; inputs that did not get their own
+    /// answer and leave the unpinned one alone.
+    ///
+    /// A key is only recorded when every solution to that part agreed on it, so a named solution
+    /// that disagrees with the main one leaves it for a person to look at.
+    pub fn record(&self, answers: &mut Answers) -> Vec<AnswerKey> {
+        let mut by_key: BTreeMap<&AnswerKey, Vec<&Outcome>> = BTreeMap::new();
+        for outcome in &self.outcomes {
+            by_key.entry(&outcome.key).or_default().push(outcome);
+        }
+
+        let mut recorded = Vec::new();
+        for (key, outcomes) in by_key {
+            let all_new = outcomes.iter().all(|o| o.status.is_new());
+            let unpinned_pass =
+                answers.get(key).is_none() && outcomes.iter().all(|o| o.status == Status::Pass);
+            let Ok(Some(answer)) = outcomes.iter().map(|o| &o.answer).all_equal_value() else {
+                continue;
+            };
+
+            if unpinned_pass {
+                answers.pin(key);
+            } else if all_new {
+                answers.insert(key.clone(), answer.clone());
+            } else {
+                continue;
+            }
+            recorded.push(key.clone());
+        }
+
+        recorded
+    }
+}
+
+/// A table with a row per outcome.
+impl fmt::Display for Verification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let header = ["input", "hash", "solution", "answer", "status"];
+        let rows = self
+            .outcomes
+            .iter()
+            .map(|outcome| {
+                [
+                    outcome.label.clone(),
+                    short(&outcome.key.input).to_string(),
+                    outcome.solution.to_string(),
+                    outcome.answer.clone().unwrap_or_else(|| "-".to_string()),
+                    outcome.status.to_string(),
+                ]
+            })
+            .collect_vec();
+
+        let widths: Vec<usize> = (0..header.len())
+            .map(|column| {
+                rows.iter()
+                    .map(|row| row[column].len())
+                    .chain([header[column].len()])
+                    .max()
+                    .unwrap_or_default()
+            })
+            .collect();
+
+        let lines = [header.map(str::to_string)].into_iter().chain(rows);
+        for row in lines {
+            let line = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{cell:width$}"))
+                .join("  ");
+            writeln!(f, "{}", line.trim_end())?;
+        }
+
+        let count = |wanted: fn(&Status) -> bool| {
+            self.outcomes.iter().filter(|o| wanted(&o.status)).count()
+        };
+        write!(
+            f,
+            "{} passed, {} failed, {} new, {} errors",
+            count(|s| *s == Status::Pass),
+            count(|s| matches!(s, Status::Fail { .. })),
+            count(Status::is_new),
+            count(|s| matches!(s, Status::Error(_)))
+        )
+    }
+}
+
+fn short(hash: &str) -> &str {
+    &hash[..hash.len().min(SHORT_HASH)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::input::InputCache;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    fn write_input(dir: &Path, day: u32, input: &str) {
+        let path = input_path(dir, YEAR, day);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, input).unwrap();
+    }
+
+    #[test]
+    fn hashes_ignore_trailing_whitespace() {
+        assert_eq!(input_hash("3   4\n"), input_hash("3   4"));
+        assert_eq!(
+            input_hash(""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn answers_file_round_trip() {
+        let text = indoc! {r#"
+            [[answer]]
+            year = 2024
+            day = 2
+            part = 1
+            input = "bbb"
+            answer = "2"
+
+            [[answer]]
+            year = 2024
+            day = 1
+            part = 1
+            input = "aaa"
+            answer = "11"
+        "#};
+
+        let answers = Answers::parse(text).unwrap();
+        assert_eq!(answers.len(), 2);
+        assert_eq!(Answers::parse(&answers.to_string()).unwrap(), answers);
+        insta::assert_snapshot!(answers.to_string());
+
+        let conflicting = format!("{text}\n[[answer]]\nyear = 2024\nday = 1\npart = 1\ninput = \"aaa\"\nanswer = \"12\"\n");
+        assert_eq!(
+            Answers::parse(&conflicting).unwrap_err().to_string(),
+            "2024 day 1 part 1 for input aaa is recorded as both 11 and 12"
+        );
+    }
+
+    #[test]
+    fn verification() {
+        let alice = tempfile::tempdir().unwrap();
+        let bob = tempfile::tempdir().unwrap();
+        write_input(
+            alice.path(),
+            1,
+            "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n",
+        );
+        write_input(bob.path(), 1, "1   1\n2   3\n");
+        write_input(bob.path(), 2, "7 6 4 2 1\n1 2 7 8 9\n");
+        write_input(bob.path(), 3, "mul(2,3)");
+
+        let alice_day1 =
+            input_hash(&fs::read_to_string(alice.path().join("2024/day1.txt")).unwrap());
+        let mut answers = Answers::default();
+        let key = |day, part, input: &str| AnswerKey {
+            year: YEAR,
+            day,
+            part,
+            input: input.to_string(),
+        };
+        answers.insert(key(1, 1, &alice_day1), "11".to_string());
+        answers.insert(key(1, 2, &alice_day1), "30".to_string());
+
+        let sets = [
+            InputSet::new("alice", alice.path()),
+            InputSet::new("bob", bob.path()),
+        ];
+        let verification = verify(&sets, &answers).unwrap();
+        assert!(verification.failed());
+        insta::assert_snapshot!(verification.to_string());
+
+        let recorded = verification.record(&mut answers);
+        assert_eq!(recorded.len(), 6);
+        assert_eq!(answers.len(), 8);
+        assert_eq!(answers.get(&key(1, 2, &alice_day1)), Some("30"));
+
+        let bob_day3 = input_hash("mul(2,3)");
+        assert_eq!(answers.get(&key(3, 1, &bob_day3)), Some("6"));
+
+        let verification = verify(&sets[1..], &answers).unwrap();
+        assert!(!verification.failed());
+        assert!(verification
+            .outcomes
+            .iter()
+            .all(|outcome| outcome.status == Status::Pass));
+    }
+
+    #[test]
+    fn unpinned_answers() {
+        let text = indoc! {r#"
+            [[answer]]
+            year = 2024
+            day = 1
+            part = 1
+            answer = "11"
+
+            [[answer]]
+            year = 2024
+            day = 1
+            part = 2
+            answer = "29"
+        "#};
+        let mut answers = Answers::parse(text).unwrap();
+        assert_eq!(answers.len(), 2);
+        assert_eq!(Answers::parse(&answers.to_string()).unwrap(), answers);
+
+        let conflicting =
+            format!("{text}\n[[answer]]\nyear = 2024\nday = 1\npart = 1\nanswer = \"12\"\n");
+        assert_eq!(
+            Answers::parse(&conflicting).unwrap_err().to_string(),
+            "2024 day 1 part 1 is recorded as both 11 and 12"
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        write_input(dir.path(), 1, "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n");
+        let verification = verify(&[InputSet::new("dave", dir.path())], &answers).unwrap();
+        let statuses = verification
+            .outcomes
+            .iter()
+            .map(|outcome| (outcome.key.part, outcome.status.clone()))
+            .dedup()
+            .collect_vec();
+        assert_eq!(
+            statuses,
+            vec![
+                (1, Status::Pass),
+                (
+                    2,
+                    Status::Unpinned {
+                        unpinned: "29".to_string()
+                    }
+                )
+            ]
+        );
+        assert!(!verification.failed());
+
+        let recorded = verification.record(&mut answers);
+        let hash = input_hash("3   4\n4   3\n2   5\n1   3\n3   9\n3   3");
+        let key = |part| AnswerKey {
+            year: YEAR,
+            day: 1,
+            part,
+            input: hash.clone(),
+        };
+        assert_eq!(recorded, vec![key(1), key(2)]);
+        assert_eq!(answers.get(&key(1)), Some("11"));
+        assert_eq!(answers.get(&key(2)), Some("31"));
+        assert_eq!(answers.unpinned(&key(1)), None);
+        assert_eq!(answers.unpinned(&key(2)), Some("29"));
+        assert_eq!(answers.len(), 3);
+        insta::assert_snapshot!(answers.to_string());
+    }
+
+    #[test]
+    fn panics_are_errors() {
+        let panicking = Solution {
+            day: 1,
+            part: 1,
+            name: None,
+            construct: |_| panic!("not ready"),
+        };
+
+        assert_eq!(
+            solve(&panicking, ArcStr::from("abc")),
+            Err("panicked: not ready".to_string())
+        );
+    }
+
+    #[test]
+    fn disagreeing_solutions_are_not_recorded() {
+        let mut answers = Answers::default();
+        let verification = Verification {
+            outcomes: ["1", "2"]
+                .into_iter()
+                .zip(SOLUTIONS.iter().filter(|s| s.day == 1 && s.part == 1))
+                .map(|(answer, solution)| Outcome {
+                    label: "carol".to_string(),
+                    solution,
+                    key: AnswerKey {
+                        year: YEAR,
+                        day: 1,
+                        part: 1,
+                        input: "ccc".to_string(),
+                    },
+                    answer: Some(answer.to_string()),
+                    status: Status::New,
+                })
+                .collect(),
+        };
+
+        assert!(verification.record(&mut answers).is_empty());
+        assert!(answers.is_empty());
+    }
+
+    /// Checks the real inputs in the input cache against `answers.toml`.
+    #[test]
+    fn registered_answers() {
+        let answers = Answers::load(Path::new(ANSWERS_PATH)).unwrap();
+        let cache = InputCache::from_env();
+        let verification = verify(&[InputSet::from_dir(cache.dir())], &answers).unwrap();
+
+        if verification.outcomes.is_empty() {
+            eprintln!(
+                "skipping answer verification: no inputs in {}",
+                cache.dir().display()
+            );
+            return;
+        }
+
+        eprintln!("{verification}");
+        assert!(!verification.failed(), "{verification}");
+        assert!(
+            verification
+                .outcomes
+                .iter()
+                .all(|outcome| !outcome.status.is_new()),
+            "{verification}\nsome inputs have no recorded answer; check them and run `aoc verify --record`"
+        );
+    }
+}
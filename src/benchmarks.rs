@@ -0,0 +1,515 @@
+//! Naming for the criterion suite in `benches/solutions.rs`, and a summary of its results.
+//!
+//! The suite times every solution's generator and solution separately, on the sample and on the
+//! real input when there is one. Criterion keeps each run's estimates under `target/criterion`,
+//! along with any baseline saved by `cargo bench --bench solutions -- --save-baseline NAME`, so
+//! the summary reads those back into a table per day and flags anything that got slower than the
+//! baseline by more than a threshold.
+
+use crate::solutions::{self, Solution};
+use color_eyre::eyre::{eyre, Result, WrapErr};
+use itertools::Itertools;
+use serde::Deserialize;
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+pub const CRITERION_DIR: &str = "target/criterion";
+/// Criterion's own `base` is overwritten by every run, so comparisons need a baseline saved by name.
+pub const DEFAULT_BASELINE: &str = "main";
+pub const DEFAULT_THRESHOLD: f64 = 0.1;
+
+const GROUP_SUFFIX: &str = "_solutions";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Stage {
+    Generator,
+    Solve,
+}
+
+impl Stage {
+    const ALL: [Stage; 2] = [Stage::Generator, Stage::Solve];
+
+    fn id(self) -> &'static str {
+        match self {
+            Stage::Generator => "generator",
+            Stage::Solve => "solve",
+        }
+    }
+}
+
+/// The criterion group holding every solution for `day`.
+#[must_use]
+pub fn group_id(day: u32) -> String {
+    format!("day{day}{GROUP_SUFFIX}")
+}
+
+/// The criterion function id for one stage of a solution, like `part2_simulated/solve`. The input
+/// goes in the benchmark's parameter.
+#[must_use]
+pub fn function_id(solution: &Solution, stage: Stage) -> String {
+    match solution.name {
+        Some(name) => format!(
+            "part{}_{}/{}",
+            solution.part,
+            name.to_lowercase(),
+            stage.id()
+        ),
+        None => format!("part{}/{}", solution.part, stage.id()),
+    }
+}
+
+/// Works out which solution and stage a criterion benchmark belongs to, if it is one of ours.
+fn parse_id(group_id: &str, function_id: &str) -> Option<(&'static Solution, Stage)> {
+    let day = group_id.strip_suffix(GROUP_SUFFIX)?.strip_prefix("day")?;
+    let (solution, stage) = function_id.split_once('/')?;
+    let (part, name) = match solution.split_once('_') {
+        Some((part, name)) => (part, Some(name)),
+        None => (solution, None),
+    };
+
+    let solution = solutions::find(
+        day.parse().ok()?,
+        part.strip_prefix("part")?.parse().ok()?,
+        name,
+    )?;
+    let stage = Stage::ALL.into_iter().find(|s| s.id() == stage)?;
+    Some((solution, stage))
+}
+
+/// The parts of criterion's `benchmark.json` that identify a benchmark.
+#[derive(Debug, Deserialize)]
+struct BenchmarkFile {
+    group_id: String,
+    function_id: Option<String>,
+    value_str: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EstimatesFile {
+    mean: PointEstimate,
+}
+
+#[derive(Debug, Deserialize)]
+struct PointEstimate {
+    /// In nanoseconds.
+    point_estimate: f64,
+}
+
+fn read_mean(path: &Path) -> Result<Option<f64>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let text =
+        fs::read_to_string(path).wrap_err_with(|| format!("could not read {}", path.display()))?;
+    let estimates: EstimatesFile = serde_json::from_str(&text)
+        .wrap_err_with(|| format!("{} is not a criterion estimates file", path.display()))?;
+    Ok(Some(estimates.mean.point_estimate))
+}
+
+/// A mean time from the latest run, and from the baseline if it has one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Estimate {
+    pub nanos: f64,
+    pub baseline_nanos: Option<f64>,
+}
+
+impl Estimate {
+    /// How much slower the latest run was than the baseline, as a fraction of the baseline.
+    #[must_use]
+    pub fn change(&self) -> Option<f64> {
+        self.baseline_nanos
+            .filter(|baseline| *baseline > 0.0)
+            .map(|baseline| (self.nanos - baseline) / baseline)
+    }
+}
+
+/// The timings of one solution on one input.
+#[derive(Debug, Clone)]
+pub struct Row {
+    pub solution: &'static Solution,
+    pub input: String,
+    pub generator: Option<Estimate>,
+    pub solve: Option<Estimate>,
+}
+
+/// A stage of a solution that slowed down by more than the threshold.
+#[derive(Debug, Clone)]
+pub struct Regression {
+    pub solution: &'static Solution,
+    pub input: String,
+    pub stage: Stage,
+    pub change: f64,
+}
+
+impl fmt::Display for Regression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} on {}: {} is {:+.1}% slower",
+            self.solution,
+            self.input,
+            self.stage.id(),
+            self.change * 100.0
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Summary {
+    pub baseline: String,
+    /// The fraction a stage may slow down by before it counts as a regression.
+    pub threshold: f64,
+    pub rows: Vec<Row>,
+}
+
+impl Summary {
+    /// Reads the suite's latest results from a criterion output directory, along with the named
+    /// baseline's where it has them.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the directory cannot be read, or holds estimates that do not parse.
+    pub fn load(criterion_dir: &Path, baseline: &str, threshold: f64) -> Result<Self> {
+        // Ordered like `SOLUTIONS`, by day, then part, with the main solution first.
+        let mut rows: BTreeMap<(u32, u32, Option<&str>, String), Row> = BTreeMap::new();
+
+        for benchmark in benchmark_files(criterion_dir)? {
+            let text = fs::read_to_string(&benchmark)
+                .wrap_err_with(|| format!("could not read {}", benchmark.display()))?;
+            let Ok(file) = serde_json::from_str::<BenchmarkFile>(&text) else {
+                continue;
+            };
+            let Some((solution, stage)) = file
+                .function_id
+                .as_deref()
+                .and_then(|function_id| parse_id(&file.group_id, function_id))
+            else {
+                continue;
+            };
+
+            // `<dir>/new/benchmark.json`, with baselines in siblings of `new`.
+            let dir = benchmark.parent().and_then(Path::parent).ok_or_else(|| {
+                eyre!(
+                    "{} is not inside a benchmark directory",
+                    benchmark.display()
+                )
+            })?;
+            let Some(nanos) = read_mean(&dir.join("new/estimates.json"))? else {
+                continue;
+            };
+            let estimate = Estimate {
+                nanos,
+                baseline_nanos: read_mean(&dir.join(baseline).join("estimates.json"))?,
+            };
+
+            let input = file.value_str.unwrap_or_default();
+            let key = (solution.day, solution.part, solution.name, input.clone());
+            let row = rows.entry(key).or_insert(Row {
+                solution,
+                input,
+                generator: None,
+                solve: None,
+            });
+            match stage {
+                Stage::Generator => row.generator = Some(estimate),
+                Stage::Solve => row.solve = Some(estimate),
+            }
+        }
+
+        Ok(Self {
+            baseline: baseline.to_string(),
+            threshold,
+            rows: rows.into_values().collect(),
+        })
+    }
+
+    #[must_use]
+    pub fn regressions(&self) -> Vec<Regression> {
+        self.rows
+            .iter()
+            .flat_map(|row| {
+                [(Stage::Generator, row.generator), (Stage::Solve, row.solve)]
+                    .into_iter()
+                    .filter_map(move |(stage, estimate)| {
+                        let change = estimate?.change()?;
+                        (change > self.threshold).then(|| Regression {
+                            solution: row.solution,
+                            input: row.input.clone(),
+                            stage,
+                            change,
+                        })
+                    })
+            })
+            .collect()
+    }
+
+    fn cells(&self, estimate: Option<Estimate>) -> [String; 2] {
+        let Some(estimate) = estimate else {
+            return ["-".to_string(), String::new()];
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let time = format!(
+            "{:.2?}",
+            Duration::from_nanos(estimate.nanos.round() as u64)
+        );
+        let change = match estimate.change() {
+            None => String::new(),
+            Some(change) if change > self.threshold => format!("{:+.1}% !", change * 100.0),
+            Some(change) => format!("{:+.1}%", change * 100.0),
+        };
+
+        [time, change]
+    }
+}
+
+/// Every `new/benchmark.json` under `dir`, sorted so the summary does not depend on the file system.
+fn benchmark_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut found = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        let entries =
+            fs::read_dir(&dir).wrap_err_with(|| format!("could not read {}", dir.display()))?;
+        for entry in entries {
+            let path = entry?.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if path.ends_with("new/benchmark.json") {
+                found.push(path);
+            }
+        }
+    }
+
+    found.sort();
+    Ok(found)
+}
+
+/// A table per day, with each stage's change against the baseline, `!` marking regressions.
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let header = [
+            "solution",
+            "input",
+            "generator",
+            "change",
+            "solve",
+            "change",
+        ]
+        .map(str::to_string);
+
+        for (day, rows) in &self.rows.iter().chunk_by(|row| row.solution.day) {
+            let rows = rows
+                .map(|row| {
+                    let [generator, generator_change] = self.cells(row.generator);
+                    let [solve, solve_change] = self.cells(row.solve);
+                    [
+                        row.solution.to_string(),
+                        row.input.clone(),
+                        generator,
+                        generator_change,
+                        solve,
+                        solve_change,
+                    ]
+                })
+                .collect_vec();
+
+            let widths: Vec<usize> = (0..header.len())
+                .map(|column| {
+                    rows.iter()
+                        .chain([&header])
+                        .map(|row| row[column].chars().count())
+                        .max()
+                        .unwrap_or_default()
+                })
+                .collect();
+
+            writeln!(f, "Day {day}")?;
+            for row in [&header].into_iter().chain(&rows) {
+                let line = row
+                    .iter()
+                    .zip(&widths)
+                    .map(|(cell, width)| format!("{cell:width$}"))
+                    .join("  ");
+                writeln!(f, "  {}", line.trim_end())?;
+            }
+            writeln!(f)?;
+        }
+
+        let compared = self
+            .rows
+            .iter()
+            .flat_map(|row| [row.generator, row.solve])
+            .flatten()
+            .any(|estimate| estimate.baseline_nanos.is_some());
+        let regressions = self.regressions().len();
+        let threshold = self.threshold * 100.0;
+
+        match (compared, regressions) {
+            (false, _) => write!(
+                f,
+                "no results for baseline `{}` to compare with",
+                self.baseline
+            ),
+            (true, 0) => write!(
+                f,
+                "no regressions above {threshold}% against baseline `{}`",
+                self.baseline
+            ),
+            (true, n) => write!(
+                f,
+                "{n} regressions above {threshold}% against baseline `{}`",
+                self.baseline
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    /// Writes a benchmark the way criterion lays it out, with `(baseline, nanos)` estimates.
+    fn write_benchmark(
+        root: &Path,
+        solution: &Solution,
+        stage: Stage,
+        input: &str,
+        runs: &[(&str, f64)],
+    ) {
+        let group_id = group_id(solution.day);
+        let function_id = function_id(solution, stage);
+        let dir = root
+            .join(&group_id)
+            .join(function_id.replace('/', "_"))
+            .join(input);
+
+        for (baseline, nanos) in runs {
+            let run = dir.join(baseline);
+            fs::create_dir_all(&run).unwrap();
+            fs::write(
+                run.join("benchmark.json"),
+                serde_json::json!({
+                    "group_id": group_id,
+                    "function_id": function_id,
+                    "value_str": input,
+                    "throughput": null,
+                    "full_id": format!("{group_id}/{function_id}/{input}"),
+                    "directory_name": "",
+                    "title": "",
+                })
+                .to_string(),
+            )
+            .unwrap();
+            fs::write(
+                run.join("estimates.json"),
+                serde_json::json!({ "mean": { "point_estimate": nanos } }).to_string(),
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn ids_round_trip() {
+        for solution in solutions::SOLUTIONS {
+            for stage in Stage::ALL {
+                let parsed = parse_id(&group_id(solution.day), &function_id(solution, stage))
+                    .map(|(solution, stage)| (solution.to_string(), stage));
+                assert_eq!(parsed, Some((solution.to_string(), stage)));
+            }
+        }
+
+        assert_eq!(
+            function_id(
+                solutions::find(6, 2, Some("Simulated")).unwrap(),
+                Stage::Solve
+            ),
+            "part2_simulated/solve"
+        );
+        assert!(parse_id("day6_part2", "jump_table/sample").is_none());
+    }
+
+    #[test]
+    fn summary() {
+        let root = tempfile::tempdir().unwrap();
+        let day1 = solutions::find(1, 1, None).unwrap();
+        let streaming = solutions::find(1, 1, Some("Streaming")).unwrap();
+        let day6 = solutions::find(6, 2, Some("Simulated")).unwrap();
+
+        write_benchmark(
+            root.path(),
+            day1,
+            Stage::Generator,
+            "sample",
+            &[("new", 1_500.0), ("main", 1_450.0)],
+        );
+        write_benchmark(
+            root.path(),
+            day1,
+            Stage::Solve,
+            "sample",
+            &[("new", 300.0), ("main", 200.0)],
+        );
+        write_benchmark(
+            root.path(),
+            streaming,
+            Stage::Solve,
+            "sample",
+            &[("new", 120.0)],
+        );
+        write_benchmark(
+            root.path(),
+            day6,
+            Stage::Generator,
+            "prod",
+            &[("new", 2_000_000.0), ("main", 2_500_000.0)],
+        );
+        write_benchmark(
+            root.path(),
+            day6,
+            Stage::Solve,
+            "prod",
+            &[("new", 1_234_567_890.0), ("main", 1_000_000_000.0)],
+        );
+
+        // Benchmarks from other suites are left out.
+        fs::create_dir_all(root.path().join("day1/nom_hashing_1000/new")).unwrap();
+        fs::write(
+            root.path().join("day1/nom_hashing_1000/new/benchmark.json"),
+            r#"{"group_id":"day1","function_id":"nom_hashing","value_str":"1000"}"#,
+        )
+        .unwrap();
+
+        let summary = Summary::load(root.path(), DEFAULT_BASELINE, DEFAULT_THRESHOLD).unwrap();
+        assert_eq!(summary.rows.len(), 3);
+        insta::assert_snapshot!(summary.to_string());
+
+        let regressions = summary
+            .regressions()
+            .iter()
+            .map(ToString::to_string)
+            .collect_vec();
+        assert_eq!(
+            regressions,
+            vec![
+                "Day 1 - Part 1 on sample: solve is +50.0% slower",
+                "Day 6 - Part 2 - Simulated on prod: solve is +23.5% slower",
+            ]
+        );
+
+        let lenient = Summary::load(root.path(), DEFAULT_BASELINE, 0.5).unwrap();
+        assert!(lenient.regressions().is_empty());
+
+        let missing = Summary::load(root.path(), "release", DEFAULT_THRESHOLD).unwrap();
+        assert!(missing.regressions().is_empty());
+        assert!(missing
+            .to_string()
+            .ends_with("no results for baseline `release` to compare with"));
+    }
+}
@@ -0,0 +1,343 @@
+//! Runs solutions on any input, without `cargo aoc`'s fixed input paths.
+//!
+//! Usage: `aoc run --day N [--part P] [--name NAME] [--input PATH|-]`, `aoc list`, or
+//! `aoc verify [--inputs DIR]... [--answers PATH] [--record]` to check every solution against the
+//! recorded answers for each directory of inputs, or
+//! `aoc bench-summary [--baseline NAME] [--threshold PERCENT] [--criterion DIR]` to tabulate the
+//! latest `cargo bench --bench solutions` run against a criterion baseline, or
+//! `aoc submit --day N --part P [--answer ANSWER] [--input PATH|-]` to send an answer, computed by
+//! the part's main solution unless given, and record it in `answers.toml` if it is right, or
+//! `aoc new --day N [--description PATH]` to start a new day's module, seeding its `SAMPLE` from a
+//! saved copy of the puzzle's description.
+
+use aoc_runner::ArcStr;
+use aoc_rust_2024::{
+    answers::{self, Answers, InputSet, ANSWERS_PATH},
+    benchmarks::{Summary, CRITERION_DIR, DEFAULT_BASELINE, DEFAULT_THRESHOLD},
+    scaffold,
+    solutions::{self, Solution, SOLUTIONS},
+    submit::{self, Client, History, Response, Submission, Verdict},
+    utils::input::InputCache,
+};
+use color_eyre::eyre::{bail, eyre, Result, WrapErr};
+use std::{
+    borrow::Borrow,
+    env, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+const USAGE: &str = "usage: aoc run --day N [--part P] [--name NAME] [--input PATH|-]
+       aoc list
+       aoc verify [--inputs DIR]... [--answers PATH] [--record]
+       aoc bench-summary [--baseline NAME] [--threshold PERCENT] [--criterion DIR]
+       aoc submit --day N --part P [--answer ANSWER] [--input PATH|-]
+       aoc new --day N [--description PATH]";
+
+fn main() -> Result<()> {
+    color_eyre::install()?;
+
+    let mut args = env::args().skip(1);
+    match args.next().as_deref() {
+        Some("run") => run(args),
+        Some("verify") => verify(args),
+        Some("bench-summary") => bench_summary(args),
+        Some("submit") => submit(args),
+        Some("new") => new_day(args),
+        Some("list") => {
+            for solution in SOLUTIONS {
+                println!("{solution}");
+            }
+            Ok(())
+        }
+        Some("-h" | "--help") => {
+            println!("{USAGE}");
+            Ok(())
+        }
+        Some(command) => bail!("unknown command {command:?}\n{USAGE}"),
+        None => bail!(USAGE),
+    }
+}
+
+fn run(mut args: impl Iterator<Item = String>) -> Result<()> {
+    let mut day = None;
+    let mut part = None;
+    let mut name = None;
+    let mut input = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" => day = Some(parse_number(&next_value(&mut args, &arg)?, &arg)?),
+            "--part" => part = Some(parse_number(&next_value(&mut args, &arg)?, &arg)?),
+            "--name" => name = Some(next_value(&mut args, &arg)?),
+            "--input" => input = Some(next_value(&mut args, &arg)?),
+            _ => bail!("unexpected argument {arg:?}\n{USAGE}"),
+        }
+    }
+
+    let day = day.ok_or_else(|| eyre!("--day is required\n{USAGE}"))?;
+    let selected: Vec<&Solution> = match (part, &name) {
+        (Some(part), name) => vec![solutions::find(day, part, name.as_deref())
+            .ok_or_else(|| eyre!("no solution for day {day} part {part}{}", describe(name)))?],
+        (None, None) => solutions::for_day(day).collect(),
+        (None, Some(_)) => bail!("--name needs --part as well"),
+    };
+    if selected.is_empty() {
+        bail!("no solutions for day {day}");
+    }
+
+    let input = read_input(day, input.as_deref())?;
+
+    for solution in selected {
+        let start = Instant::now();
+        let runner = (solution.construct)(input.clone())
+            .map_err(|e| eyre!("{e}"))
+            .wrap_err_with(|| format!("{solution}: the generator failed"))?;
+        let generated = Instant::now();
+        let answer = runner
+            .try_run()
+            .map_err(|e| eyre!("{e}"))
+            .wrap_err_with(|| format!("{solution}: the solution failed"))?;
+        let finished = Instant::now();
+
+        println!(
+            "{solution}: {answer}\n\tgenerator: {:?},\n\trunner: {:?}",
+            generated - start,
+            finished - generated
+        );
+    }
+
+    Ok(())
+}
+
+fn verify(mut args: impl Iterator<Item = String>) -> Result<()> {
+    let mut sets = Vec::new();
+    let mut answers_path = PathBuf::from(ANSWERS_PATH);
+    let mut record = false;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--inputs" => sets.push(InputSet::from_dir(next_value(&mut args, &arg)?)),
+            "--answers" => answers_path = next_value(&mut args, &arg)?.into(),
+            "--record" => record = true,
+            _ => bail!("unexpected argument {arg:?}\n{USAGE}"),
+        }
+    }
+    if sets.is_empty() {
+        sets.push(InputSet::from_dir(InputCache::from_env().dir()));
+    }
+
+    let mut answers = Answers::load(&answers_path)?;
+    let verification = answers::verify(&sets, &answers)?;
+    if verification.outcomes.is_empty() {
+        bail!("none of the input directories have any inputs");
+    }
+    println!("{verification}");
+
+    if record {
+        let recorded = verification.record(&mut answers);
+        if !recorded.is_empty() {
+            answers.save(&answers_path)?;
+        }
+
+        println!(
+            "\nrecorded {} answers in {}",
+            recorded.len(),
+            answers_path.display()
+        );
+        for key in recorded {
+            println!("\t{key}: {}", answers.get(&key).unwrap_or_default());
+        }
+    }
+
+    if verification.failed() {
+        bail!("some solutions did not match their recorded answers");
+    }
+
+    Ok(())
+}
+
+fn bench_summary(mut args: impl Iterator<Item = String>) -> Result<()> {
+    let mut baseline = DEFAULT_BASELINE.to_string();
+    let mut threshold = DEFAULT_THRESHOLD;
+    let mut criterion_dir = PathBuf::from(CRITERION_DIR);
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--baseline" => baseline = next_value(&mut args, &arg)?,
+            "--threshold" => {
+                let value = next_value(&mut args, &arg)?;
+                let percent: f64 = value
+                    .parse()
+                    .ok()
+                    .filter(|percent: &f64| *percent >= 0.0)
+                    .ok_or_else(|| eyre!("{arg} expects a percentage, got {value:?}"))?;
+                threshold = percent / 100.0;
+            }
+            "--criterion" => criterion_dir = next_value(&mut args, &arg)?.into(),
+            _ => bail!("unexpected argument {arg:?}\n{USAGE}"),
+        }
+    }
+
+    let summary = Summary::load(&criterion_dir, &baseline, threshold).wrap_err(
+        "could not read the benchmark results, has `cargo bench --bench solutions` run?",
+    )?;
+    if summary.rows.is_empty() {
+        bail!(
+            "{} has no results from `cargo bench --bench solutions`",
+            criterion_dir.display()
+        );
+    }
+    println!("{summary}");
+
+    let regressions = summary.regressions();
+    if !regressions.is_empty() {
+        for regression in &regressions {
+            eprintln!("{regression}");
+        }
+        bail!("{} benchmarks regressed", regressions.len());
+    }
+
+    Ok(())
+}
+
+fn submit(mut args: impl Iterator<Item = String>) -> Result<()> {
+    let mut day = None;
+    let mut part = None;
+    let mut answer = None;
+    let mut input = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" => day = Some(parse_number(&next_value(&mut args, &arg)?, &arg)?),
+            "--part" => part = Some(parse_number(&next_value(&mut args, &arg)?, &arg)?),
+            "--answer" => answer = Some(next_value(&mut args, &arg)?),
+            "--input" => input = Some(next_value(&mut args, &arg)?),
+            _ => bail!("unexpected argument {arg:?}\n{USAGE}"),
+        }
+    }
+
+    let day = day.ok_or_else(|| eyre!("--day is required\n{USAGE}"))?;
+    let part = part.ok_or_else(|| eyre!("--part is required\n{USAGE}"))?;
+    let input = read_input(day, input.as_deref());
+
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let solution = solutions::find(day, part, None)
+                .ok_or_else(|| eyre!("no solution for day {day} part {part}"))?;
+            let input = input.as_ref().map_err(|e| eyre!("{e:#}"))?;
+            let runner = (solution.construct)(input.clone())
+                .map_err(|e| eyre!("{e}"))
+                .wrap_err_with(|| format!("{solution}: the generator failed"))?;
+            let answer = runner
+                .try_run()
+                .map_err(|e| eyre!("{e}"))
+                .wrap_err_with(|| format!("{solution}: the solution failed"))?;
+            answer.to_string()
+        }
+    };
+
+    let client = Client::from_env()?;
+    let history_path = submit::history_path(InputCache::from_env().dir(), client.year());
+    let mut history = History::load(&history_path)?;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+
+    let response = match client.submit(&mut history, day, part, &answer, now)? {
+        Submission::Blocked(blocked) => bail!("not submitting {answer}: {blocked}"),
+        Submission::Sent(response) => response,
+    };
+    history.save(&history_path)?;
+    println!("day {day} part {part}: {answer} is {response}");
+
+    if let Response::Judged {
+        verdict: Verdict::Correct,
+        ..
+    } = response
+    {
+        let Ok(input) = input else {
+            eprintln!("not recording {answer} in {ANSWERS_PATH}: the input it belongs to is not available");
+            return Ok(());
+        };
+
+        let answers_path = Path::new(ANSWERS_PATH);
+        let mut answers = Answers::load(answers_path)?;
+        let key = submit::record_answer(&mut answers, day, part, input.borrow(), &answer);
+        answers.save(answers_path)?;
+        println!("recorded {key} in {ANSWERS_PATH}");
+    }
+
+    Ok(())
+}
+
+fn new_day(mut args: impl Iterator<Item = String>) -> Result<()> {
+    let mut day = None;
+    let mut description = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" => day = Some(parse_number(&next_value(&mut args, &arg)?, &arg)?),
+            "--description" => description = Some(next_value(&mut args, &arg)?),
+            _ => bail!("unexpected argument {arg:?}\n{USAGE}"),
+        }
+    }
+
+    let day = day.ok_or_else(|| eyre!("--day is required\n{USAGE}"))?;
+    let sample = match description {
+        Some(path) => {
+            let text =
+                fs::read_to_string(&path).wrap_err_with(|| format!("could not read {path}"))?;
+            Some(
+                scaffold::extract_sample(&text)
+                    .ok_or_else(|| eyre!("{path} does not have an example in it"))?,
+            )
+        }
+        None => None,
+    };
+
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    for path in scaffold::scaffold(root, day, sample.as_deref())? {
+        println!("wrote {}", path.display());
+    }
+
+    Ok(())
+}
+
+/// Reads `path`, or stdin for `-`, defaulting to the day's cached input, fetched if need be.
+fn read_input(day: u32, path: Option<&str>) -> Result<ArcStr> {
+    let text = match path {
+        Some("-") => {
+            let mut text = String::new();
+            io::stdin()
+                .read_to_string(&mut text)
+                .wrap_err("could not read stdin")?;
+            text
+        }
+        Some(path) => {
+            fs::read_to_string(path).wrap_err_with(|| format!("could not read {path}"))?
+        }
+        None => InputCache::from_env().load(day)?,
+    };
+
+    Ok(ArcStr::from(text.trim_end()))
+}
+
+fn describe(name: &Option<String>) -> String {
+    name.as_ref()
+        .map(|name| format!(" named {name:?}"))
+        .unwrap_or_default()
+}
+
+fn next_value(args: &mut impl Iterator<Item = String>, flag: &str) -> Result<String> {
+    args.next()
+        .ok_or_else(|| eyre!("{flag} needs a value\n{USAGE}"))
+}
+
+fn parse_number(value: &str, flag: &str) -> Result<u32> {
+    value
+        .parse()
+        .wrap_err_with(|| format!("{flag} expects a number, not {value:?}"))
+}
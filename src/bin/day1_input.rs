@@ -0,0 +1,53 @@
+//! Writes a synthetic day1 input of any size to stdout, for testing how the solutions scale.
+//!
+//! Usage: `day1_input <pairs> [--seed N]`
+
+use aoc_rust_2024::day1::synthetic_input;
+use color_eyre::eyre::{bail, eyre, Result, WrapErr};
+use std::{
+    env,
+    io::{self, Write},
+};
+
+const USAGE: &str = "usage: day1_input <pairs> [--seed N]";
+
+fn main() -> Result<()> {
+    color_eyre::install()?;
+
+    let mut pairs = None;
+    let mut seed = 2024;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seed" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| eyre!("--seed needs a value\n{USAGE}"))?;
+                seed = value
+                    .parse()
+                    .wrap_err_with(|| format!("invalid seed {value:?}"))?;
+            }
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return Ok(());
+            }
+            _ if pairs.is_none() => {
+                pairs = Some(
+                    arg.replace('_', "")
+                        .parse::<usize>()
+                        .wrap_err_with(|| format!("invalid pair count {arg:?}"))?,
+                );
+            }
+            _ => bail!("unexpected argument {arg:?}\n{USAGE}"),
+        }
+    }
+
+    let pairs = pairs.ok_or_else(|| eyre!(USAGE))?;
+
+    io::stdout()
+        .lock()
+        .write_all(synthetic_input(pairs, seed).as_bytes())?;
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+//! Draws the day6 guard's patrol, optionally with an extra obstacle and as an animation.
+//!
+//! Usage: `patrol <input> [--obstacle X,Y] [--animate] [--delay MS]`
+
+use aoc_rust_2024::{
+    day6::{animate_patrol, patrol_overlay},
+    utils::point::Point,
+};
+use color_eyre::eyre::{bail, eyre, Result, WrapErr};
+use std::{env, fs, io, time::Duration};
+
+const USAGE: &str = "usage: patrol <input> [--obstacle X,Y] [--animate] [--delay MS]";
+
+fn main() -> Result<()> {
+    color_eyre::install()?;
+
+    let mut input = None;
+    let mut obstacle = None;
+    let mut animate = false;
+    let mut delay = Duration::from_millis(50);
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--obstacle" => obstacle = Some(parse_point(&next_value(&mut args, &arg)?)?),
+            "--animate" => animate = true,
+            "--delay" => {
+                let millis = next_value(&mut args, &arg)?;
+                delay = Duration::from_millis(
+                    millis
+                        .parse()
+                        .wrap_err_with(|| format!("invalid delay {millis:?}"))?,
+                );
+            }
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return Ok(());
+            }
+            _ if input.is_none() => input = Some(arg),
+            _ => bail!("unexpected argument {arg:?}\n{USAGE}"),
+        }
+    }
+
+    let path = input.ok_or_else(|| eyre!(USAGE))?;
+    let map = fs::read_to_string(&path).wrap_err_with(|| format!("could not read {path}"))?;
+    let map = map.trim_end();
+
+    if animate {
+        animate_patrol(map, obstacle, delay, &mut io::stdout().lock())
+    } else {
+        println!("{}", patrol_overlay(map, obstacle)?);
+        Ok(())
+    }
+}
+
+fn next_value(args: &mut impl Iterator<Item = String>, flag: &str) -> Result<String> {
+    args.next()
+        .ok_or_else(|| eyre!("{flag} needs a value\n{USAGE}"))
+}
+
+fn parse_point(value: &str) -> Result<Point<usize>> {
+    let (x, y) = value
+        .split_once(',')
+        .ok_or_else(|| eyre!("expected X,Y but got {value:?}"))?;
+
+    Ok(Point::new(
+        x.trim().parse().wrap_err("invalid x coordinate")?,
+        y.trim().parse().wrap_err("invalid y coordinate")?,
+    ))
+}
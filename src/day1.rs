@@ -2,9 +2,13 @@ use aoc_runner_derive::{aoc, aoc_generator};
 use color_eyre::eyre::{eyre, Result};
 use itertools::Itertools;
 use once_cell::sync::Lazy;
+use std::fmt::Write;
 
 static COLOR_EYRE: Lazy<()> = Lazy::new(|| color_eyre::install().unwrap());
 
+/// # Errors
+///
+/// Fails if the input is not lines of two whitespace separated numbers.
 #[aoc_generator(day1)]
 pub fn input_generator(input: &str) -> Result<(Vec<u64>, Vec<u64>)> {
     Lazy::get(&COLOR_EYRE);
@@ -12,6 +16,7 @@ pub fn input_generator(input: &str) -> Result<(Vec<u64>, Vec<u64>)> {
 }
 
 #[aoc(day1, part1)]
+#[must_use]
 pub fn part1(lists: &(Vec<u64>, Vec<u64>)) -> u64 {
     let mut left = lists.0.clone();
     let mut right = lists.1.clone();
@@ -26,6 +31,7 @@ pub fn part1(lists: &(Vec<u64>, Vec<u64>)) -> u64 {
 }
 
 #[aoc(day1, part2)]
+#[must_use]
 pub fn part2(lists: &(Vec<u64>, Vec<u64>)) -> u64 {
     let left = lists.0.clone();
     let right = lists.1.clone();
@@ -37,6 +43,186 @@ pub fn part2(lists: &(Vec<u64>, Vec<u64>)) -> u64 {
         .sum()
 }
 
+/// Both location lists, each sorted ascending, for inputs too large to clone and hash.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortedLists {
+    left: Vec<u64>,
+    right: Vec<u64>,
+}
+
+impl SortedLists {
+    /// Parses `left   right` lines straight into buffers sized from the line count, then radix
+    /// sorts both lists.
+    ///
+    /// # Errors
+    ///
+    /// Fails on the first line that is not two whitespace separated numbers, or has one too large
+    /// for a `u64`.
+    pub fn parse(input: &str) -> Result<Self> {
+        let bytes = input.as_bytes();
+        let lines = input.lines().count();
+
+        let mut left = Vec::with_capacity(lines);
+        let mut right = Vec::with_capacity(lines);
+        let mut position = 0;
+
+        while position < bytes.len() {
+            let line = left.len() + 1;
+
+            let (first, rest) = parse_number(&bytes[position..], line, "a location ID")?;
+            let separator = rest
+                .iter()
+                .take_while(|b| **b == b' ' || **b == b'\t')
+                .count();
+            if separator == 0 {
+                return Err(eyre!("line {line}: expected whitespace after {first}"));
+            }
+            let (second, rest) = parse_number(&rest[separator..], line, "a second location ID")?;
+
+            let line_ending = match rest {
+                [] => 0,
+                [b'\n', ..] => 1,
+                [b'\r', b'\n', ..] => 2,
+                _ => return Err(eyre!("line {line}: unexpected text after {second}")),
+            };
+
+            left.push(first);
+            right.push(second);
+            position = bytes.len() - rest.len() + line_ending;
+        }
+
+        let mut scratch = vec![0; left.len()];
+        radix_sort(&mut left, &mut scratch);
+        radix_sort(&mut right, &mut scratch);
+
+        Ok(Self { left, right })
+    }
+}
+
+/// Parses the leading digits of `bytes`, returning the number and whatever follows it. Errors
+/// name the `line` and, when there are no digits, `expected` as what should have been there.
+fn parse_number<'a>(bytes: &'a [u8], line: usize, expected: &str) -> Result<(u64, &'a [u8])> {
+    let digits = bytes.iter().take_while(|b| b.is_ascii_digit()).count();
+    if digits == 0 {
+        return Err(eyre!("line {line}: expected {expected}"));
+    }
+
+    let number = bytes[..digits]
+        .iter()
+        .try_fold(0u64, |number, digit| {
+            number.checked_mul(10)?.checked_add(u64::from(digit - b'0'))
+        })
+        .ok_or_else(|| {
+            eyre!(
+                "line {line}: location ID {} is out of range",
+                String::from_utf8_lossy(&bytes[..digits])
+            )
+        })?;
+
+    Ok((number, &bytes[digits..]))
+}
+
+/// Sorts `values` with a least significant byte first radix sort, using `scratch` as the second
+/// buffer. Bytes above the largest value's highest set byte are skipped, so small IDs take only a
+/// few passes.
+///
+/// # Panics
+///
+/// Panics if `scratch` is shorter than `values`.
+fn radix_sort(values: &mut [u64], scratch: &mut [u64]) {
+    let scratch = &mut scratch[..values.len()];
+    let max = values.iter().copied().max().unwrap_or_default();
+    let passes = (u64::BITS - max.leading_zeros()).div_ceil(8) as usize;
+
+    for byte in 0..passes {
+        let digit = |value: u64| usize::from(value.to_le_bytes()[byte]);
+
+        let mut offsets = [0usize; 256];
+        for value in values.iter() {
+            offsets[digit(*value)] += 1;
+        }
+        let mut total = 0;
+        for offset in &mut offsets {
+            (*offset, total) = (total, total + *offset);
+        }
+
+        for value in values.iter() {
+            let slot = &mut offsets[digit(*value)];
+            scratch[*slot] = *value;
+            *slot += 1;
+        }
+        values.copy_from_slice(scratch);
+    }
+}
+
+#[aoc_generator(day1, part1, Streaming)]
+#[aoc_generator(day1, part2, Streaming)]
+fn streaming_generator(input: &str) -> Result<SortedLists> {
+    Lazy::get(&COLOR_EYRE);
+    SortedLists::parse(input)
+}
+
+#[aoc(day1, part1, Streaming)]
+#[must_use]
+pub fn part1_streaming(lists: &SortedLists) -> u64 {
+    lists
+        .left
+        .iter()
+        .zip(&lists.right)
+        .map(|(left, right)| left.abs_diff(*right))
+        .sum()
+}
+
+/// Computes the similarity score by walking both sorted lists together, multiplying each run of
+/// equal left IDs by the length of the matching run on the right.
+#[aoc(day1, part2, Streaming)]
+#[must_use]
+pub fn part2_streaming(lists: &SortedLists) -> u64 {
+    let (left, right) = (&lists.left[..], &lists.right[..]);
+    let (mut i, mut j) = (0, 0);
+    let mut score = 0;
+
+    while i < left.len() && j < right.len() {
+        let id = left[i];
+
+        if right[j] < id {
+            j += 1;
+            continue;
+        }
+
+        let left_run = left[i..].iter().take_while(|x| **x == id).count();
+        let right_run = right[j..].iter().take_while(|x| **x == id).count();
+        score += id * (left_run * right_run) as u64;
+
+        i += left_run;
+        j += right_run;
+    }
+
+    score
+}
+
+/// Generates `pairs` lines of puzzle shaped input from `seed`, drawing five digit IDs so that the
+/// lists share plenty of values.
+#[must_use]
+pub fn synthetic_input(pairs: usize, seed: u64) -> String {
+    // xorshift64*, which is plenty random for benchmarking and needs no extra dependency.
+    let mut state = seed | 1;
+    let mut next_id = move || {
+        state ^= state >> 12;
+        state ^= state << 25;
+        state ^= state >> 27;
+        10_000 + state.wrapping_mul(0x2545_f491_4f6c_dd1d) % 90_000
+    };
+
+    let mut input = String::with_capacity(pairs * 14);
+    for _ in 0..pairs {
+        let (left, right) = (next_id(), next_id());
+        writeln!(input, "{left}   {right}").unwrap();
+    }
+
+    input
+}
+
 mod parsers {
     use nom::{
         character::complete::{newline, space1, u64 as u64_parser},
@@ -51,7 +237,9 @@ mod parsers {
 
     type Span<'a> = LocatedSpan<&'a str>;
 
-    pub(crate) fn parse_input(input: &str) -> color_eyre::Result<(Vec<u64>, Vec<u64>), ParseError> {
+    pub(crate) fn parse_input(
+        input: &str,
+    ) -> color_eyre::Result<(Vec<u64>, Vec<u64>), ParseError<'_>> {
         final_parser(lists)(Span::new(input))
     }
 
@@ -81,7 +269,6 @@ mod tests {
     use indoc::indoc;
     use pretty_assertions::assert_eq;
     use rstest::rstest;
-    use std::fs;
 
     const SAMPLE: &str = indoc! {
         "3   4
@@ -110,14 +297,73 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_streaming_parsing() {
+        let lists = SortedLists::parse(SAMPLE).unwrap();
+
+        assert_eq!(lists.left, vec![1, 2, 3, 3, 3, 4]);
+        assert_eq!(lists.right, vec![3, 3, 3, 4, 5, 9]);
+
+        let crlf = SortedLists::parse("3   4\r\n4\t3\n").unwrap();
+        assert_eq!(crlf.left, vec![3, 4]);
+        assert_eq!(crlf.right, vec![3, 4]);
+    }
+
     #[rstest]
-    #[case::part1(part1, 936_063)]
-    #[case::part2(part2, 23_150_395)]
-    fn prod_tests(#[case] f: fn(&(Vec<u64>, Vec<u64>)) -> u64, #[case] expected: u64) {
-        let input = fs::read_to_string("input/2024/day1.txt").unwrap();
-        let parsed = input_generator(input.trim_end()).unwrap();
-        let result = f(&parsed);
+    #[case("3   4\n4", "line 2: expected whitespace after 4")]
+    #[case("3   4\n4   x", "line 2: expected a second location ID")]
+    #[case("3   4 5", "line 1: unexpected text after 4")]
+    #[case("   4", "line 1: expected a location ID")]
+    #[case(
+        "99999999999999999999   1",
+        "line 1: location ID 99999999999999999999 is out of range"
+    )]
+    #[case(
+        "1   18446744073709551616",
+        "line 1: location ID 18446744073709551616 is out of range"
+    )]
+    fn test_streaming_errors(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(SortedLists::parse(input).unwrap_err().to_string(), expected);
+    }
 
-        assert_eq!(result, expected);
+    #[rstest]
+    #[case(vec![])]
+    #[case(vec![5])]
+    #[case(vec![3, 1, 2])]
+    #[case(vec![u64::MAX, 0, 1 << 40, 255, 256, 1 << 40, 7])]
+    fn test_radix_sort(#[case] mut values: Vec<u64>) {
+        let mut expected = values.clone();
+        expected.sort_unstable();
+
+        let mut scratch = vec![0; values.len()];
+        radix_sort(&mut values, &mut scratch);
+
+        assert_eq!(values, expected);
+    }
+
+    #[rstest]
+    #[case::part1(part1, part1_streaming)]
+    #[case::part2(part2, part2_streaming)]
+    fn streaming_matches(
+        #[case] f: fn(&(Vec<u64>, Vec<u64>)) -> u64,
+        #[case] streaming: fn(&SortedLists) -> u64,
+    ) {
+        for (pairs, seed) in [(1, 1), (100, 2), (10_000, 3)] {
+            let input = synthetic_input(pairs, seed);
+
+            let expected = f(&input_generator(input.trim_end()).unwrap());
+            let result = streaming(&SortedLists::parse(&input).unwrap());
+
+            assert_eq!(result, expected);
+        }
+    }
+
+    #[rstest]
+    #[case::part1(part1_streaming, 11)]
+    #[case::part2(part2_streaming, 31)]
+    fn streaming_sample_tests(#[case] f: fn(&SortedLists) -> u64, #[case] expected: u64) {
+        let parsed = SortedLists::parse(SAMPLE).unwrap();
+
+        assert_eq!(f(&parsed), expected);
     }
 }
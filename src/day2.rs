@@ -2,58 +2,237 @@ use aoc_runner_derive::{aoc, aoc_generator};
 use color_eyre::eyre::{eyre, Result};
 use itertools::Itertools;
 use once_cell::sync::Lazy;
-use std::cmp::Reverse;
+use std::{fmt, ops::RangeInclusive};
 
 static COLOR_EYRE: Lazy<()> = Lazy::new(|| color_eyre::install().unwrap());
 
+/// One report's levels, in order.
 #[derive(Clone, Default, Debug)]
-pub(crate) struct Report(Vec<u64>);
+pub struct Report(Vec<u64>);
 
 #[aoc_generator(day2)]
-pub fn input_generator(input: &str) -> Result<Vec<Report>> {
+fn input_generator(input: &str) -> Result<Vec<Report>> {
     Lazy::get(&COLOR_EYRE);
     parsers::parse_input(input).map_err(|e| eyre!(e.to_string()))
 }
 
 #[aoc(day2, part1)]
-pub fn part1(reports: &[Report]) -> u64 {
+fn part1(reports: &[Report]) -> u64 {
     reports.iter().filter(|rep| rep.is_safe()).count() as u64
 }
 
+/// The default distance adjacent levels must be apart for a report to be safe.
+const STEP_BOUNDS: RangeInclusive<u64> = 1..=3;
+
+/// Which way a report's levels are heading.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trend {
+    Increasing,
+    Decreasing,
+}
+
+/// What is wrong with a pair of adjacent levels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepFault {
+    /// The levels head the other way from the rest of the report, or stay level.
+    WrongDirection,
+    /// The levels head the right way, but by a step outside the allowed bounds.
+    OutOfBounds { step: u64 },
+}
+
+/// The first pair of adjacent levels, by index, that breaks a report's trend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnsafeStep {
+    pub from_index: usize,
+    pub to_index: usize,
+    pub from: u64,
+    pub to: u64,
+    pub fault: StepFault,
+}
+
+/// Whether a report is safe and, if not, why not.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SafetyVerdict {
+    Safe {
+        trend: Trend,
+    },
+    /// Safe once the Problem Dampener removes the level at `removed`.
+    Dampened {
+        trend: Trend,
+        removed: usize,
+        problem: UnsafeStep,
+    },
+    /// Unsafe even with the Problem Dampener, reporting the problem for the trend that gets
+    /// furthest through the report.
+    Unsafe {
+        trend: Trend,
+        problem: UnsafeStep,
+    },
+}
+
 impl Report {
+    #[must_use]
+    pub fn levels(&self) -> &[u64] {
+        &self.0
+    }
+
     fn is_safe(&self) -> bool {
-        if !(self.is_asc() || self.is_desc()) {
-            return false;
+        matches!(self.verdict(&STEP_BOUNDS), SafetyVerdict::Safe { .. })
+    }
+
+    fn is_dampened_safe(&self) -> bool {
+        !matches!(self.verdict(&STEP_BOUNDS), SafetyVerdict::Unsafe { .. })
+    }
+
+    /// Judges the report, requiring adjacent levels to differ by an amount within `bounds` and
+    /// allowing the Problem Dampener to remove a single level.
+    ///
+    /// Any removal that fixes a report must take out one of the two levels of its first unsafe
+    /// step, since every step before it is already fine, so only those two need trying. That
+    /// keeps the check to a few linear scans.
+    #[must_use]
+    pub fn verdict(&self, bounds: &RangeInclusive<u64>) -> SafetyVerdict {
+        let problems = [Trend::Increasing, Trend::Decreasing]
+            .map(|trend| (trend, self.first_unsafe_step(trend, None, bounds)));
+
+        let unsafe_trends = match problems {
+            [(trend, None), _] | [_, (trend, None)] => return SafetyVerdict::Safe { trend },
+            [(first, Some(first_problem)), (second, Some(second_problem))] => {
+                [(first, first_problem), (second, second_problem)]
+            }
+        };
+
+        for (trend, problem) in &unsafe_trends {
+            let fixed = [problem.from_index, problem.to_index]
+                .into_iter()
+                .find(|skip| {
+                    self.first_unsafe_step(*trend, Some(*skip), bounds)
+                        .is_none()
+                });
+
+            if let Some(removed) = fixed {
+                return SafetyVerdict::Dampened {
+                    trend: *trend,
+                    removed,
+                    problem: *problem,
+                };
+            }
         }
 
+        let [first, second] = unsafe_trends;
+        let (trend, problem) = if second.1.from_index > first.1.from_index {
+            second
+        } else {
+            first
+        };
+        SafetyVerdict::Unsafe { trend, problem }
+    }
+
+    /// The first pair of adjacent levels, ignoring the one at `skip`, that does not follow `trend`
+    /// by a step within `bounds`.
+    fn first_unsafe_step(
+        &self,
+        trend: Trend,
+        skip: Option<usize>,
+        bounds: &RangeInclusive<u64>,
+    ) -> Option<UnsafeStep> {
         self.0
             .iter()
+            .copied()
+            .enumerate()
+            .filter(|(i, _)| Some(*i) != skip)
             .tuple_windows()
-            .all(|(a, b)| (1..=3).contains(&a.abs_diff(*b)))
+            .find_map(|((from_index, from), (to_index, to))| {
+                let (low, high) = match trend {
+                    Trend::Increasing => (from, to),
+                    Trend::Decreasing => (to, from),
+                };
+
+                let fault = if low >= high {
+                    StepFault::WrongDirection
+                } else if !bounds.contains(&(high - low)) {
+                    StepFault::OutOfBounds { step: high - low }
+                } else {
+                    return None;
+                };
+
+                Some(UnsafeStep {
+                    from_index,
+                    to_index,
+                    from,
+                    to,
+                    fault,
+                })
+            })
     }
+}
 
-    fn is_dampened_safe(&self) -> bool {
-        if self.is_safe() {
-            return true;
+impl fmt::Display for Trend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Trend::Increasing => write!(f, "increasing"),
+            Trend::Decreasing => write!(f, "decreasing"),
         }
+    }
+}
 
-        let k = self.0.len() - 1;
-        let options = self.0.iter().copied().combinations_with_replacement(k);
+impl fmt::Display for UnsafeStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "levels {} and {} ({} -> {}) ",
+            self.from_index, self.to_index, self.from, self.to
+        )?;
 
-        options.map(Report).any(|opt| opt.is_safe())
+        match self.fault {
+            StepFault::WrongDirection => write!(f, "break the trend"),
+            StepFault::OutOfBounds { step } => write!(f, "change by {step}"),
+        }
     }
+}
 
-    fn is_asc(&self) -> bool {
-        self.0.is_sorted()
+impl fmt::Display for SafetyVerdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SafetyVerdict::Safe { trend } => write!(f, "safe, {trend}"),
+            SafetyVerdict::Dampened {
+                trend,
+                removed,
+                problem,
+            } => write!(
+                f,
+                "safe, {trend}, after removing level {removed} since {problem}"
+            ),
+            SafetyVerdict::Unsafe { trend, problem } => {
+                write!(f, "unsafe, at best {trend} until {problem}")
+            }
+        }
     }
+}
 
-    fn is_desc(&self) -> bool {
-        self.0.is_sorted_by_key(|x| Reverse(*x))
-    }
+/// Judges each report in `input` with adjacent levels required to differ by an amount within
+/// `bounds`, a line per report.
+///
+/// # Errors
+///
+/// Fails if the input does not parse.
+pub fn verdicts(input: &str, bounds: &RangeInclusive<u64>) -> Result<String> {
+    let reports = input_generator(input)?;
+
+    Ok(reports
+        .iter()
+        .map(|report| {
+            format!(
+                "{}: {}",
+                report.levels().iter().join(" "),
+                report.verdict(bounds)
+            )
+        })
+        .join("\n"))
 }
 
 #[aoc(day2, part2)]
-pub fn part2(reports: &[Report]) -> u64 {
+fn part2(reports: &[Report]) -> u64 {
     reports.iter().filter(|rep| rep.is_dampened_safe()).count() as u64
 }
 
@@ -71,7 +250,7 @@ mod parsers {
 
     type Span<'a> = LocatedSpan<&'a str>;
 
-    pub(crate) fn parse_input(input: &str) -> color_eyre::Result<Vec<Report>, ParseError> {
+    pub(crate) fn parse_input(input: &str) -> color_eyre::Result<Vec<Report>, ParseError<'_>> {
         final_parser(reports)(Span::new(input))
     }
 
@@ -90,8 +269,8 @@ mod tests {
     use super::*;
     use indoc::indoc;
     use pretty_assertions::assert_eq;
+    use proptest::prelude::*;
     use rstest::rstest;
-    use std::fs;
 
     const SAMPLE: &str = indoc! {
         "7 6 4 2 1
@@ -117,15 +296,107 @@ mod tests {
 
         assert_eq!(result, expected);
     }
-    //
+
     #[rstest]
-    #[case::part1(part1, 202)]
-    #[case::part2(part2, 271)]
-    fn prod_tests(#[case] f: fn(&[Report]) -> u64, #[case] expected: u64) {
-        let input = fs::read_to_string("input/2024/day2.txt").unwrap();
-        let parsed = input_generator(input.trim_end()).unwrap();
-        let result = f(&parsed);
+    #[case::default_bounds("verdicts", 1..=3)]
+    #[case::wide_bounds("verdicts_wide", 1..=5)]
+    fn test_verdicts(#[case] name: &str, #[case] bounds: RangeInclusive<u64>) {
+        insta::assert_snapshot!(name, verdicts(SAMPLE, &bounds).unwrap());
+    }
 
-        assert_eq!(result, expected);
+    #[test]
+    fn test_verdict_details() {
+        let report = Report(vec![1, 3, 2, 4, 5]);
+
+        assert_eq!(
+            report.verdict(&STEP_BOUNDS),
+            SafetyVerdict::Dampened {
+                trend: Trend::Increasing,
+                removed: 1,
+                problem: UnsafeStep {
+                    from_index: 1,
+                    to_index: 2,
+                    from: 3,
+                    to: 2,
+                    fault: StepFault::WrongDirection,
+                },
+            }
+        );
+
+        let report = Report(vec![9, 7, 6, 2, 1]);
+        assert_eq!(
+            report.verdict(&STEP_BOUNDS),
+            SafetyVerdict::Unsafe {
+                trend: Trend::Decreasing,
+                problem: UnsafeStep {
+                    from_index: 2,
+                    to_index: 3,
+                    from: 6,
+                    to: 2,
+                    fault: StepFault::OutOfBounds { step: 4 },
+                },
+            }
+        );
+        assert_eq!(
+            report.verdict(&(1..=4)),
+            SafetyVerdict::Safe {
+                trend: Trend::Decreasing
+            }
+        );
+    }
+
+    /// The dampener by definition: the report itself, or any copy of it with one level removed.
+    fn brute_force_dampened(report: &Report) -> bool {
+        report.is_safe()
+            || (0..report.0.len()).any(|i| {
+                let mut levels = report.0.clone();
+                levels.remove(i);
+                Report(levels).is_safe()
+            })
+    }
+
+    #[rstest]
+    #[case(vec![1, 5, 6, 7], true)]
+    #[case(vec![1, 2, 3, 9], true)]
+    #[case(vec![5, 1, 2, 3], true)]
+    #[case(vec![1, 2, 2, 3], true)]
+    #[case(vec![3, 1, 2, 4, 5], true)]
+    #[case(vec![1, 2, 1, 2], false)]
+    #[case(vec![1, 5, 9, 10], false)]
+    #[case(vec![4], true)]
+    fn dampening(#[case] levels: Vec<u64>, #[case] expected: bool) {
+        let report = Report(levels);
+
+        assert_eq!(report.is_dampened_safe(), expected);
+        assert_eq!(brute_force_dampened(&report), expected);
+    }
+
+    proptest! {
+        #[test]
+        fn dampener_matches_brute_force(levels in prop::collection::vec(0..20u64, 0..10)) {
+            let report = Report(levels);
+            prop_assert_eq!(report.is_dampened_safe(), brute_force_dampened(&report));
+        }
+
+        #[test]
+        fn dampener_matches_brute_force_near_safe(
+            start in 25..40u64,
+            steps in prop::collection::vec(1..=3u64, 1..8),
+            descending: bool,
+            outlier in (0..9usize, 0..30u64),
+        ) {
+            let mut levels = steps
+                .iter()
+                .scan(start, |level, step| {
+                    *level = if descending { *level - step } else { *level + step };
+                    Some(*level)
+                })
+                .collect_vec();
+            let (index, value) = outlier;
+            levels.insert(index.min(levels.len()), value);
+
+            let report = Report(levels);
+            prop_assert_eq!(report.is_dampened_safe(), brute_force_dampened(&report));
+        }
     }
 }
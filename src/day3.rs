@@ -1,63 +1,273 @@
 use aoc_runner_derive::{aoc, aoc_generator};
 use color_eyre::eyre::{eyre, Result};
 use once_cell::sync::Lazy;
+use std::{fmt, ops::Range};
 
 static COLOR_EYRE: Lazy<()> = Lazy::new(|| color_eyre::install().unwrap());
 
-#[aoc_generator(day3, part1)]
-fn parse(input: &str) -> Result<Vec<Mul>> {
+#[aoc_generator(day3)]
+fn parse(input: &str) -> Result<Vec<Instruction>> {
     Lazy::get(&COLOR_EYRE);
-    parsers::parse_input(input).map_err(|e| eyre!(e.to_string()))
+    tokenize(input, &Registry::default())
 }
 
-#[aoc_generator(day3, part2)]
-fn parse_2(input: &str) -> Result<Vec<Instruction>> {
-    Lazy::get(&COLOR_EYRE);
-    parsers::parse_input_2(input).map_err(|e| eyre!(e.to_string()))
+/// Reads every instruction `registry` knows of out of corrupted memory, ignoring everything else.
+///
+/// # Errors
+///
+/// Fails only if the parser itself does, since unrecognised memory is skipped.
+pub fn tokenize(input: &str, registry: &Registry) -> Result<Vec<Instruction>> {
+    parsers::parse_input(input, registry).map_err(|e| eyre!(e.to_string()))
+}
+
+/// What running an [`Opcode`] does to the machine.
+#[derive(Clone, Copy)]
+pub enum Effect {
+    /// Computes a value from the arguments, which is added to the total at the top level. `None`
+    /// means the arguments are out of the opcode's domain, and the instruction does nothing.
+    Value(fn(&[u64]) -> Option<u64>),
+    /// Turns the machine on or off.
+    Enable(bool),
 }
 
-#[derive(Debug, Copy, Clone)]
-struct Mul {
-    x: u64,
-    y: u64,
+/// An instruction the corrupted memory can contain, written `name(arg,...)` with exactly `arity`
+/// arguments.
+#[derive(Clone, Copy)]
+pub struct Opcode {
+    pub name: &'static str,
+    pub arity: usize,
+    pub effect: Effect,
+}
+
+impl fmt::Debug for Opcode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Opcode").field(&self.name).finish()
+    }
 }
 
-#[derive(Debug, Copy, Clone)]
-enum Instruction {
-    Do,
-    Dont,
-    Multiply(Mul),
+pub const MUL: Opcode = Opcode {
+    name: "mul",
+    arity: 2,
+    effect: Effect::Value(|args| args[0].checked_mul(args[1])),
+};
+
+pub const DO: Opcode = Opcode {
+    name: "do",
+    arity: 0,
+    effect: Effect::Enable(true),
+};
+
+pub const DONT: Opcode = Opcode {
+    name: "don't",
+    arity: 0,
+    effect: Effect::Enable(false),
+};
+
+/// The opcodes the tokenizer recognises. New ones only need registering here; the parser reads
+/// every opcode the same way.
+#[derive(Debug, Clone)]
+pub struct Registry {
+    opcodes: Vec<Opcode>,
+    /// The most digits a numeric operand may have. The puzzle allows one to three.
+    max_digits: usize,
+    /// Whether arguments may themselves be instructions, like `mul(add(1,2),3)`.
+    nesting: bool,
+}
+
+impl Registry {
+    #[must_use]
+    pub fn empty() -> Self {
+        Self {
+            opcodes: vec![],
+            max_digits: 3,
+            nesting: false,
+        }
+    }
+
+    #[must_use]
+    pub fn with(mut self, opcode: Opcode) -> Self {
+        self.opcodes.push(opcode);
+        self
+    }
+
+    #[must_use]
+    pub fn with_max_digits(mut self, max_digits: usize) -> Self {
+        self.max_digits = max_digits;
+        self
+    }
+
+    #[must_use]
+    pub fn with_nesting(mut self) -> Self {
+        self.nesting = true;
+        self
+    }
+}
+
+/// The puzzle's `mul`, `do` and `don't`.
+impl Default for Registry {
+    fn default() -> Self {
+        Self::empty().with(MUL).with(DO).with(DONT)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Argument {
+    Number(u64),
+    Call(Instruction),
+}
+
+/// A recognised instruction, with the byte range of the memory it was read from.
+#[derive(Debug, Clone)]
+pub struct Instruction {
+    pub opcode: Opcode,
+    pub arguments: Vec<Argument>,
+    pub span: Range<usize>,
+}
+
+impl Instruction {
+    /// The value of a [`Effect::Value`] instruction, evaluating nested instructions first.
+    #[must_use]
+    pub fn value(&self) -> Option<u64> {
+        let Effect::Value(compute) = self.opcode.effect else {
+            return None;
+        };
+
+        let arguments = self
+            .arguments
+            .iter()
+            .map(|argument| match argument {
+                Argument::Number(n) => Some(*n),
+                Argument::Call(instruction) => instruction.value(),
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        compute(&arguments)
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}(", self.opcode.name)?;
+        for (i, argument) in self.arguments.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            match argument {
+                Argument::Number(n) => write!(f, "{n}")?,
+                Argument::Call(instruction) => write!(f, "{instruction}")?,
+            }
+        }
+        write!(f, ")")
+    }
+}
+
+/// Something that looks like an instruction but is not one, such as `mul[3,7]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NearMiss {
+    pub fragment: String,
+    pub line: u32,
+    pub column: usize,
+    pub reason: String,
+}
+
+impl fmt::Display for NearMiss {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{} {} {}",
+            self.line, self.column, self.fragment, self.reason
+        )
+    }
+}
+
+/// Lists every fragment of corrupted memory where a registered opcode's name is followed by a
+/// bracket, but which still is not a valid instruction.
+///
+/// # Errors
+///
+/// Fails only if the parser itself does, since unrecognised memory is skipped.
+pub fn near_misses(input: &str, registry: &Registry) -> Result<Vec<NearMiss>> {
+    parsers::parse_near_misses(input, registry).map_err(|e| eyre!(e.to_string()))
+}
+
+/// Runs instructions, adding up their values while enabled.
+#[derive(Debug, Clone)]
+pub struct Machine {
+    /// Whether `do` and `don't` style instructions have any effect.
+    obey_toggles: bool,
+    enabled: bool,
+    total: u64,
+}
+
+impl Machine {
+    #[must_use]
+    pub fn new(obey_toggles: bool) -> Self {
+        Self {
+            obey_toggles,
+            enabled: true,
+            total: 0,
+        }
+    }
+
+    /// # Errors
+    ///
+    /// Fails if adding the instruction's value overflows the total, which wider operands or
+    /// nesting make possible.
+    pub fn execute(&mut self, instruction: &Instruction) -> Result<()> {
+        match instruction.opcode.effect {
+            Effect::Enable(enabled) => {
+                if self.obey_toggles {
+                    self.enabled = enabled;
+                }
+            }
+            Effect::Value(_) if self.enabled => {
+                if let Some(value) = instruction.value() {
+                    self.total = self.total.checked_add(value).ok_or_else(|| {
+                        eyre!(
+                            "adding {instruction} at {:?} overflows the total of {}",
+                            instruction.span,
+                            self.total
+                        )
+                    })?;
+                }
+            }
+            Effect::Value(_) => {}
+        }
+
+        Ok(())
+    }
+
+    /// # Errors
+    ///
+    /// Fails if the total overflows.
+    pub fn run(mut self, program: &[Instruction]) -> Result<u64> {
+        for instruction in program {
+            self.execute(instruction)?;
+        }
+
+        Ok(self.total)
+    }
 }
 
 #[aoc(day3, part1)]
-fn part1(input: &[Mul]) -> u64 {
-    input.iter().map(|m| m.x * m.y).sum()
+fn part1(program: &[Instruction]) -> Result<u64> {
+    Machine::new(false).run(program)
 }
 
 #[aoc(day3, part2)]
-fn part2(input: &[Instruction]) -> u64 {
-    let res = input.iter().fold(
-        (0, true),
-        |acc @ (sum, do_mul), instruction| match instruction {
-            Instruction::Do => (sum, true),
-            Instruction::Dont => (sum, false),
-            Instruction::Multiply(Mul { x, y }) if do_mul => (sum + x * y, do_mul),
-            Instruction::Multiply(_) => acc,
-        },
-    );
-
-    res.0
+fn part2(program: &[Instruction]) -> Result<u64> {
+    Machine::new(true).run(program)
 }
 
 mod parsers {
-    use crate::day3::{Instruction, Mul};
+    use crate::day3::{Argument, Instruction, NearMiss, Opcode, Registry};
     use nom::{
-        branch::alt,
-        bytes::complete::tag,
-        character::{complete, complete::anychar},
-        combinator::value,
-        multi::many1,
-        sequence::{delimited, separated_pair},
+        bytes::complete::{tag, take_while, take_while_m_n},
+        character::complete::{anychar, one_of},
+        combinator::{consumed, opt, value, verify},
+        error::{ErrorKind, ParseError as _},
+        multi::{many0, separated_list0},
+        sequence::delimited,
         IResult, Parser,
     };
     use nom_locate::LocatedSpan;
@@ -67,60 +277,207 @@ mod parsers {
 
     type Span<'a> = LocatedSpan<&'a str>;
 
-    pub(crate) fn parse_input(input: &str) -> color_eyre::Result<Vec<Mul>, ParseError> {
-        final_parser(program)(Span::new(input))
+    pub(super) fn parse_input<'a>(
+        input: &'a str,
+        registry: &Registry,
+    ) -> color_eyre::Result<Vec<Instruction>, ParseError<'a>> {
+        final_parser(|input| program(registry, input))(Span::new(input))
     }
 
-    pub(crate) fn parse_input_2(input: &str) -> color_eyre::Result<Vec<Instruction>, ParseError> {
-        final_parser(program_2)(Span::new(input))
+    pub(super) fn parse_near_misses<'a>(
+        input: &'a str,
+        registry: &Registry,
+    ) -> color_eyre::Result<Vec<NearMiss>, ParseError<'a>> {
+        final_parser(|input| near_miss_scan(registry, input))(Span::new(input))
     }
 
-    fn program(input: Span) -> IResult<Span, Vec<Mul>, ParseError> {
-        many1(alt((mul.map(Some), value(None, anychar))))
+    /// Reads every instruction in the memory, skipping a character at a time past anything else.
+    fn program<'a>(
+        registry: &Registry,
+        input: Span<'a>,
+    ) -> IResult<Span<'a>, Vec<Instruction>, ParseError<'a>> {
+        many0(alt_call_or_skip(registry))
             .map(|x| x.into_iter().flatten().collect())
             .parse(input)
     }
 
-    fn program_2(input: Span) -> IResult<Span, Vec<Instruction>, ParseError> {
-        many1(alt((
-            alt((mul_inst, do_inst, dont_inst)).map(Some),
-            value(None, anychar),
+    fn alt_call_or_skip<'a, 'r>(
+        registry: &'r Registry,
+    ) -> impl FnMut(Span<'a>) -> IResult<Span<'a>, Option<Instruction>, ParseError<'a>> + 'r {
+        move |input| match call(registry, input) {
+            Ok((rest, instruction)) => Ok((rest, Some(instruction))),
+            Err(nom::Err::Error(_)) => value(None, anychar).parse(input),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Any registered opcode followed by the right number of parenthesised arguments.
+    fn call<'a>(
+        registry: &Registry,
+        input: Span<'a>,
+    ) -> IResult<Span<'a>, Instruction, ParseError<'a>> {
+        for opcode in &registry.opcodes {
+            let parsed = tag(opcode.name)
+                .precedes(delimited(
+                    tag("("),
+                    verify(
+                        separated_list0(tag(","), |input| argument(registry, input)),
+                        |arguments: &Vec<Argument>| arguments.len() == opcode.arity,
+                    ),
+                    tag(")"),
+                ))
+                .parse(input);
+
+            if let Ok((rest, arguments)) = parsed {
+                let span = input.location_offset()..rest.location_offset();
+                return Ok((
+                    rest,
+                    Instruction {
+                        opcode: *opcode,
+                        arguments,
+                        span,
+                    },
+                ));
+            }
+        }
+
+        Err(nom::Err::Error(ParseError::from_error_kind(
+            input,
+            ErrorKind::Tag,
         )))
-        .map(|x| x.into_iter().flatten().collect())
-        .parse(input)
     }
 
-    fn do_inst(input: Span) -> IResult<Span, Instruction, ParseError> {
-        tag("do()").map(|_| Instruction::Do).parse(input)
+    fn argument<'a>(
+        registry: &Registry,
+        input: Span<'a>,
+    ) -> IResult<Span<'a>, Argument, ParseError<'a>> {
+        match operand(registry.max_digits)
+            .map(Argument::Number)
+            .parse(input)
+        {
+            Err(nom::Err::Error(_)) if registry.nesting => {
+                call(registry, input).map(|(rest, instruction)| (rest, Argument::Call(instruction)))
+            }
+            result => result,
+        }
     }
 
-    fn dont_inst(input: Span) -> IResult<Span, Instruction, ParseError> {
-        tag("don't()").map(|_| Instruction::Dont).parse(input)
+    /// A number of one to `max_digits` digits. Longer numbers are not cut short, they fail on the
+    /// digit that follows.
+    fn operand<'a>(max_digits: usize) -> impl Parser<Span<'a>, u64, ParseError<'a>> {
+        take_while_m_n(1, max_digits, |c: char| c.is_ascii_digit())
+            .map_res(|digits: Span<'a>| digits.fragment().parse::<u64>())
     }
 
-    fn mul(input: Span) -> IResult<Span, Mul, ParseError> {
-        tag("mul")
-            .precedes(delimited(
-                tag("("),
-                separated_pair(complete::u64, tag(","), complete::u64),
-                tag(")"),
-            ))
-            .map(|(x, y)| Mul { x, y })
-            .parse(input)
+    /// Skips past valid instructions, recording near misses and stepping over everything else a
+    /// character at a time.
+    fn near_miss_scan<'a>(
+        registry: &Registry,
+        input: Span<'a>,
+    ) -> IResult<Span<'a>, Vec<NearMiss>, ParseError<'a>> {
+        many0(|input| {
+            if let Ok((rest, _)) = call(registry, input) {
+                return Ok((rest, None));
+            }
+
+            match near_miss(registry, input) {
+                Ok((_, near_miss)) => {
+                    let (rest, _) = anychar(input)?;
+                    Ok((rest, Some(near_miss)))
+                }
+                Err(_) => value(None, anychar).parse(input),
+            }
+        })
+        .map(|x| x.into_iter().flatten().collect())
+        .parse(input)
+    }
+
+    /// An opcode name and an opening bracket, then as much of an argument list as is there.
+    fn near_miss<'a>(
+        registry: &Registry,
+        input: Span<'a>,
+    ) -> IResult<Span<'a>, NearMiss, ParseError<'a>> {
+        for opcode in &registry.opcodes {
+            let parsed: IResult<_, _, ParseError<'a>> = consumed(
+                tag(opcode.name)
+                    .precedes(one_of("([{<"))
+                    .and(take_while(|c: char| {
+                        c.is_ascii_alphanumeric() || matches!(c, ',' | ' ' | '-')
+                    }))
+                    .and(opt(one_of(")]}>"))),
+            )
+            .parse(input);
+
+            if let Ok((rest, (fragment, ((open, body), close)))) = parsed {
+                let reason = near_miss_reason(opcode, registry.max_digits, open, &body, close);
+
+                return Ok((
+                    rest,
+                    NearMiss {
+                        fragment: fragment.fragment().to_string(),
+                        line: fragment.location_line(),
+                        column: fragment.get_utf8_column(),
+                        reason,
+                    },
+                ));
+            }
+        }
+
+        Err(nom::Err::Error(ParseError::from_error_kind(
+            input,
+            ErrorKind::Tag,
+        )))
     }
 
-    fn mul_inst(input: Span) -> IResult<Span, Instruction, ParseError> {
-        mul.map(Instruction::Multiply).parse(input)
+    fn near_miss_reason(
+        opcode: &Opcode,
+        max_digits: usize,
+        open: char,
+        body: &str,
+        close: Option<char>,
+    ) -> String {
+        let operands: Vec<&str> = if body.is_empty() {
+            vec![]
+        } else {
+            body.split(',').collect()
+        };
+
+        if open != '(' {
+            return format!("opens with '{open}' instead of '('");
+        }
+        if let Some(operand) = operands
+            .iter()
+            .find(|operand| !operand.chars().all(|c| c.is_ascii_digit()) || operand.is_empty())
+        {
+            return format!("has {operand:?}, which is not a number");
+        }
+        if let Some(operand) = operands.iter().find(|operand| operand.len() > max_digits) {
+            return format!("has {operand}, which is longer than {max_digits} digits");
+        }
+        match close {
+            None => return "is never closed".to_string(),
+            Some(close) if close != ')' => return format!("closes with '{close}' instead of ')'"),
+            Some(_) => {}
+        }
+        if operands.len() != opcode.arity {
+            let plural = if operands.len() == 1 { "" } else { "s" };
+            return format!(
+                "has {} operand{plural} but {} takes {}",
+                operands.len(),
+                opcode.name,
+                opcode.arity
+            );
+        }
+
+        "is not a valid instruction".to_string()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::day3::parsers::{parse_input, parse_input_2};
     use indoc::indoc;
     use pretty_assertions::assert_eq;
-    use std::fs;
 
     const SAMPLE: &str = indoc! {
         "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))"
@@ -130,49 +487,137 @@ mod tests {
         "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))"
     };
 
+    const ADD: Opcode = Opcode {
+        name: "add",
+        arity: 2,
+        effect: Effect::Value(|args| args[0].checked_add(args[1])),
+    };
+
+    const SUB: Opcode = Opcode {
+        name: "sub",
+        arity: 2,
+        effect: Effect::Value(|args| args[0].checked_sub(args[1])),
+    };
+
+    fn tokens(input: &str, registry: &Registry) -> Vec<String> {
+        tokenize(input, registry)
+            .unwrap()
+            .iter()
+            .map(|instruction| format!("{instruction} at {:?}", instruction.span))
+            .collect()
+    }
+
     #[test]
     fn test_parsing() {
-        let muls = parse(SAMPLE).unwrap();
-        insta::assert_debug_snapshot!(muls);
+        let instructions = parse(SAMPLE).unwrap();
+        insta::assert_debug_snapshot!(instructions);
     }
 
     #[test]
     fn test_parsing2() {
-        let inst = parse_2(SAMPLE2).unwrap();
-        insta::assert_debug_snapshot!(inst);
+        let instructions = tokens(SAMPLE2, &Registry::default());
+        insta::assert_debug_snapshot!(instructions);
     }
 
     #[test]
-    fn sample_1() {
-        let parsed = parse_input(SAMPLE).unwrap();
-        let result = part1(&parsed);
+    fn test_spans() {
+        let instructions = parse(SAMPLE2).unwrap();
 
-        assert_eq!(result, 161);
+        for instruction in &instructions {
+            assert_eq!(&SAMPLE2[instruction.span.clone()], instruction.to_string());
+        }
     }
 
     #[test]
-    fn sample_2() {
-        let parsed = parse_input_2(SAMPLE2).unwrap();
-        let result = part2(&parsed);
+    fn test_registered_opcodes() {
+        let registry = Registry::default().with(ADD).with(SUB);
+        let program = tokenize("add(2,3)sub(1,5)sub(9,4)don't()mul(2,2)add(1)", &registry).unwrap();
+
+        assert_eq!(program.len(), 5);
+        assert_eq!(Machine::new(false).run(&program).unwrap(), 5 + 5 + 4);
+        assert_eq!(Machine::new(true).run(&program).unwrap(), 5 + 5);
+        assert!(tokens("add(2,3)", &Registry::default()).is_empty());
+    }
 
-        assert_eq!(result, 48);
+    #[test]
+    fn test_nesting() {
+        let input = "mul(add(1,2),mul(2,2))mul(3,add(1,1)";
+
+        assert_eq!(
+            tokens(input, &Registry::default().with(ADD)),
+            vec![
+                "add(1,2) at 4..12",
+                "mul(2,2) at 13..21",
+                "add(1,1) at 28..36"
+            ]
+        );
+
+        let registry = Registry::default().with(ADD).with_nesting();
+        assert_eq!(
+            tokens(input, &registry),
+            vec!["mul(add(1,2),mul(2,2)) at 0..22", "add(1,1) at 28..36"]
+        );
+
+        let program = tokenize(input, &registry).unwrap();
+        assert_eq!(Machine::new(false).run(&program).unwrap(), 12 + 2);
+    }
+
+    #[test]
+    fn test_overflow() {
+        let registry = Registry::default().with_max_digits(10);
+        let program = tokenize("mul(4294967296,4294967295)mul(4294967296,1)", &registry).unwrap();
+
+        assert_eq!(
+            Machine::new(false).run(&program).unwrap_err().to_string(),
+            "adding mul(4294967296,1) at 26..43 overflows the total of 18446744069414584320"
+        );
     }
 
     #[test]
-    fn prod_1() {
-        let input = fs::read_to_string("input/2024/day3.txt").unwrap();
-        let parsed = parse_input(&input).unwrap();
-        let result = part1(&parsed);
+    fn test_operand_width() {
+        assert_eq!(
+            tokens(
+                "mul(123,4)mul(1234,5)mul(6,7890)mul(0,12)",
+                &Registry::default()
+            ),
+            vec!["mul(123,4) at 0..10", "mul(0,12) at 32..41"]
+        );
+        assert_eq!(
+            tokens("mul(1234,5)", &Registry::default().with_max_digits(4)),
+            vec!["mul(1234,5) at 0..11"]
+        );
+    }
+
+    #[test]
+    fn test_near_misses() {
+        let input = indoc! {
+            "mul(4,5)mul[3,7]mul(32,64]do_not_mul(5,5)
+             mul ( 2 , 4 )do(1)mul(1234,5)mul(6,x)don't(mul(2,3)
+             mul(1,2,3)mul(4"
+        };
+
+        let misses = [SAMPLE, SAMPLE2, input]
+            .iter()
+            .flat_map(|input| near_misses(input, &Registry::default()).unwrap())
+            .map(|near_miss| near_miss.to_string())
+            .collect::<Vec<_>>();
+
+        insta::assert_debug_snapshot!(misses);
+    }
+
+    #[test]
+    fn sample_1() {
+        let parsed = parse(SAMPLE).unwrap();
+        let result = part1(&parsed).unwrap();
 
-        assert_eq!(result, 153_469_856);
+        assert_eq!(result, 161);
     }
 
     #[test]
-    fn prod_2() {
-        let input = fs::read_to_string("input/2024/day3.txt").unwrap();
-        let parsed = parse_input_2(&input).unwrap();
-        let result = part2(&parsed);
+    fn sample_2() {
+        let parsed = parse(SAMPLE2).unwrap();
+        let result = part2(&parsed).unwrap();
 
-        assert_eq!(result, 77_055_967);
+        assert_eq!(result, 48);
     }
 }
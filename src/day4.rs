@@ -1,116 +1,200 @@
-use crate::utils::point::Point;
+use crate::utils::{direction::Ordinal, grid::Grid, point::Point};
 use aoc_runner_derive::{aoc, aoc_generator};
+use color_eyre::eyre::{Report, Result};
 use itertools::Itertools;
 use once_cell::sync::Lazy;
+use std::str::FromStr;
 use strum::IntoEnumIterator;
-use strum_macros::EnumIter;
 
 static COLOR_EYRE: Lazy<()> = Lazy::new(|| color_eyre::install().unwrap());
 
+/// The puzzle's X-MAS: two `MAS` crossing on their `A`, in any orientation.
+const X_MAS: &str = "M.S\n.A.\nM.S";
+
 #[aoc_generator(day4)]
-fn parse(input: &str) -> Vec<Vec<char>> {
+fn parse(input: &str) -> Result<Grid<char>> {
     Lazy::get(&COLOR_EYRE);
-    input
-        .lines()
-        .map(|l| l.chars().collect::<Vec<char>>())
-        .collect::<Vec<Vec<char>>>()
+    input.parse()
 }
 
-#[derive(Clone, Copy, EnumIter, Debug)]
-enum Direction {
-    Down,
-    Up,
-    Left,
-    Right,
-    DiagonalRightDown,
-    DiagonalLeftDown,
-    DiagonalRightUp,
-    DiagonalLeftUp,
+#[aoc(day4, part1)]
+fn part1(search_space: &Grid<char>) -> usize {
+    find_words(search_space, &["XMAS"]).len()
 }
 
-#[aoc(day4, part1)]
-fn part1(input: &Vec<Vec<char>>) -> usize {
-    let search_space = input.clone();
-    let size = search_space.len();
-
-    (0..size)
-        .cartesian_product(0..size)
-        .map(|(x, y)| Point { x, y })
-        .cartesian_product(Direction::iter())
-        .filter(|(pos, direction)| search_direction(&search_space, pos, *direction, "XMAS"))
-        .count()
-}
-
-fn search_direction(
-    grid: &Vec<Vec<char>>,
-    pos: &Point<usize>,
-    direction: Direction,
-    word: &str,
-) -> bool {
-    if word.is_empty() {
-        return true;
-    }
+#[aoc(day4, part2)]
+fn part2(search_space: &Grid<char>) -> usize {
+    let pattern: Pattern = X_MAS.parse().unwrap();
+    find_pattern(search_space, &pattern).len()
+}
+
+/// A word found in the grid, reading from `start` towards `direction`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WordMatch<'w> {
+    pub word: &'w str,
+    pub start: Point<usize>,
+    pub direction: Ordinal,
+}
 
-    let first_letter = word.chars().next().unwrap();
+impl WordMatch<'_> {
+    /// The points covered by the word, in reading order.
+    pub fn points<'g>(&self, grid: &'g Grid<char>) -> impl Iterator<Item = Point<usize>> + 'g {
+        let offset = self.direction.to_offset();
+        let len = self.word.chars().count();
 
-    if grid.get(pos.y).and_then(|row| row.get(pos.x)) != Some(&first_letter) {
-        return false;
+        std::iter::successors(Some(self.start), move |point| grid.step(*point, offset)).take(len)
     }
+}
 
-    let rest = &word[1..];
+/// Finds every occurrence of each of `words` reading in any of the eight directions, in
+/// reading order of their starting points.
+#[must_use]
+pub fn find_words<'w>(grid: &Grid<char>, words: &[&'w str]) -> Vec<WordMatch<'w>> {
+    grid.points()
+        .cartesian_product(Ordinal::iter())
+        .cartesian_product(words)
+        .filter(|((start, direction), word)| reads(grid, *start, *direction, word))
+        .map(|((start, direction), word)| WordMatch {
+            word,
+            start,
+            direction,
+        })
+        .collect()
+}
 
-    let max_index = grid.len() - 1;
-    let next_pos = match direction {
-        Direction::Down if pos.y < max_index => Point::new(pos.x, pos.y + 1),
-        Direction::Up if pos.y > 0 => Point::new(pos.x, pos.y - 1),
-        Direction::Left if pos.x > 0 => Point::new(pos.x - 1, pos.y),
-        Direction::Right if pos.x < max_index => Point::new(pos.x + 1, pos.y),
-        Direction::DiagonalRightDown if pos.x < max_index && pos.y < max_index => {
-            Point::new(pos.x + 1, pos.y + 1)
-        }
-        Direction::DiagonalLeftDown if pos.x > 0 && pos.y < max_index => {
-            Point::new(pos.x - 1, pos.y + 1)
-        }
-        Direction::DiagonalRightUp if pos.x < max_index && pos.y > 0 => {
-            Point::new(pos.x + 1, pos.y - 1)
+/// Whether `word` can be read from `start` towards `direction` without leaving the grid.
+fn reads(grid: &Grid<char>, start: Point<usize>, direction: Ordinal, word: &str) -> bool {
+    let offset = direction.to_offset();
+    let mut point = Some(start);
+
+    word.chars().all(|letter| {
+        let Some(current) = point else {
+            return false;
+        };
+
+        point = grid.step(current, offset);
+        grid[current] == letter
+    })
+}
+
+/// A rectangular block of letters to look for, where `.` matches any letter. It matches in any
+/// of its four quarter turn rotations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    /// The distinct rotations, so a symmetric pattern is not counted more than once per place.
+    rotations: Vec<Grid<Option<char>>>,
+}
+
+impl FromStr for Pattern {
+    type Err = Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let cells = Grid::parse_with(s, |c| Ok((c != '.').then_some(c)))?;
+
+        let mut rotations = vec![cells];
+        for _ in 0..3 {
+            let next = rotations.last().unwrap().rotate_clockwise();
+            if !rotations.contains(&next) {
+                rotations.push(next);
+            }
         }
-        Direction::DiagonalLeftUp if pos.x > 0 && pos.y > 0 => Point::new(pos.x - 1, pos.y - 1),
-        _ if rest.is_empty() => return true,
-        _ => return false,
-    };
 
-    search_direction(grid, &next_pos, direction, &word[1..])
+        Ok(Self { rotations })
+    }
 }
 
-#[aoc(day4, part2)]
-fn part2(search_space: &Vec<Vec<char>>) -> usize {
-    let size = search_space.len();
-
-    (0..size)
-        .cartesian_product(0..size)
-        .filter_map(|pt| pairs(pt.into(), size))
-        .filter(|(a, b)| {
-            [
-                ("MAS", "MAS"),
-                ("MAS", "SAM"),
-                ("SAM", "MAS"),
-                ("SAM", "SAM"),
-            ]
+/// A placement of a [`Pattern`] in the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PatternMatch {
+    pub top_left: Point<usize>,
+    /// Which of the pattern's distinct rotations matched, in clockwise quarter turns.
+    pub rotation: usize,
+}
+
+impl PatternMatch {
+    /// The grid points covered by the pattern's letters, leaving out its wildcards.
+    pub fn points<'p>(&self, pattern: &'p Pattern) -> impl Iterator<Item = Point<usize>> + 'p {
+        let top_left = self.top_left;
+
+        pattern.rotations[self.rotation]
             .iter()
-            .any(|(x, y)| {
-                search_direction(search_space, a, Direction::DiagonalRightDown, x)
-                    && search_direction(search_space, b, Direction::DiagonalLeftDown, y)
-            })
+            .filter(|(_, cell)| cell.is_some())
+            .map(move |(point, _)| top_left + point)
+    }
+}
+
+/// Finds every placement of `pattern`, in any rotation, that lies entirely on the grid.
+#[must_use]
+pub fn find_pattern(grid: &Grid<char>, pattern: &Pattern) -> Vec<PatternMatch> {
+    grid.points()
+        .cartesian_product(pattern.rotations.iter().enumerate())
+        .filter(|(top_left, (_, cells))| {
+            let bottom_right = *top_left + Point::new(cells.width() - 1, cells.height() - 1);
+
+            grid.in_bounds(bottom_right)
+                && cells.iter().all(|(offset, cell)| {
+                    cell.is_none_or(|letter| grid[*top_left + offset] == letter)
+                })
         })
-        .count()
+        .map(|(top_left, (rotation, _))| PatternMatch { top_left, rotation })
+        .collect()
+}
+
+/// How [`render_highlights`] marks the letters that are part of a match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    /// Keeps matched letters and replaces the rest with `.`, as the puzzle text does.
+    Plain,
+    /// Like [`Style::Plain`], but also colours each match differently with ANSI escapes.
+    Ansi,
 }
 
-fn pairs(point: Point<usize>, grid_size: usize) -> Option<(Point<usize>, Point<usize>)> {
-    if point.x + 2 >= grid_size {
-        return None;
+/// Colours cycled through by [`Style::Ansi`], as SGR foreground codes.
+const PALETTE: [u8; 6] = [31, 32, 33, 34, 35, 36];
+
+/// Draws the grid keeping only the letters covered by `matches`, where each match is the points
+/// it covers. A letter covered by more than one match takes the colour of the first.
+pub fn render_highlights<I, P>(grid: &Grid<char>, matches: I, style: Style) -> String
+where
+    I: IntoIterator<Item = P>,
+    P: IntoIterator<Item = Point<usize>>,
+{
+    owners(grid, matches)
+        .rows()
+        .enumerate()
+        .map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .map(|(x, owner)| {
+                    let letter = grid[Point::new(x, y)];
+                    match (owner, style) {
+                        (None, _) => ".".to_string(),
+                        (Some(_), Style::Plain) => letter.to_string(),
+                        (Some(index), Style::Ansi) => {
+                            let colour = PALETTE[index % PALETTE.len()];
+                            format!("\x1b[1;{colour}m{letter}\x1b[0m")
+                        }
+                    }
+                })
+                .collect::<String>()
+        })
+        .join("\n")
+}
+
+/// The index of the first match covering each point of the grid.
+fn owners<I, P>(grid: &Grid<char>, matches: I) -> Grid<Option<usize>>
+where
+    I: IntoIterator<Item = P>,
+    P: IntoIterator<Item = Point<usize>>,
+{
+    let mut owners = Grid::new(grid.width(), grid.height(), None);
+    for (index, points) in matches.into_iter().enumerate() {
+        for point in points {
+            owners[point].get_or_insert(index);
+        }
     }
 
-    Some((point, Point::new(point.x + 2, point.y)))
+    owners
 }
 
 #[cfg(test)]
@@ -119,7 +203,6 @@ mod tests {
     use indoc::indoc;
     use pretty_assertions::assert_eq;
     use rstest::rstest;
-    use std::fs;
 
     const SAMPLE: &str = indoc! {
       "MMMSXXMASM
@@ -136,54 +219,129 @@ mod tests {
 
     #[test]
     fn test_parsing() {
-        let reports = parse(SAMPLE);
+        let reports = parse(SAMPLE).unwrap();
         insta::assert_debug_snapshot!(reports);
     }
 
     #[rstest]
     #[case::part1(part1, 18)]
     #[case::part2(part2, 9)]
-    fn sample_tests(#[case] f: fn(&Vec<Vec<char>>) -> usize, #[case] expected: usize) {
-        let parsed = parse(SAMPLE);
+    fn sample_tests(#[case] f: fn(&Grid<char>) -> usize, #[case] expected: usize) {
+        let parsed = parse(SAMPLE).unwrap();
         let result = f(&parsed);
 
         assert_eq!(result, expected);
     }
 
     #[rstest]
-    #[case((5, 0), Direction::Right)]
-    #[case((4, 0), Direction::DiagonalRightDown)]
-    #[case((4, 1), Direction::Left)]
-    #[case((9, 3), Direction::Down)]
-    #[case((0, 4), Direction::Right)]
-    #[case((6, 4), Direction::Left)]
-    #[case((6, 4), Direction::Up)]
-    #[case((0, 5), Direction::DiagonalRightUp)]
-    #[case((6, 5), Direction::DiagonalLeftUp)]
-    #[case((1, 9), Direction::DiagonalRightUp)]
-    #[case((3, 9), Direction::DiagonalLeftUp)]
-    #[case((5, 9), Direction::DiagonalLeftUp)]
-    #[case((5, 9), Direction::Right)]
-    #[case((9, 9), Direction::Up)]
-    fn search(#[case] (x, y): (usize, usize), #[case] direction: Direction) {
-        let parsed = parse(SAMPLE);
-
-        assert!(search_direction(
-            &parsed,
-            &Point { x, y },
+    #[case((5, 0), Ordinal::East)]
+    #[case((4, 0), Ordinal::SouthEast)]
+    #[case((4, 1), Ordinal::West)]
+    #[case((9, 3), Ordinal::South)]
+    #[case((0, 4), Ordinal::East)]
+    #[case((6, 4), Ordinal::West)]
+    #[case((6, 4), Ordinal::North)]
+    #[case((0, 5), Ordinal::NorthEast)]
+    #[case((6, 5), Ordinal::NorthWest)]
+    #[case((1, 9), Ordinal::NorthEast)]
+    #[case((3, 9), Ordinal::NorthWest)]
+    #[case((5, 9), Ordinal::NorthWest)]
+    #[case((5, 9), Ordinal::East)]
+    #[case((9, 9), Ordinal::North)]
+    fn search(#[case] (x, y): (usize, usize), #[case] direction: Ordinal) {
+        let parsed = parse(SAMPLE).unwrap();
+
+        assert!(find_words(&parsed, &["XMAS"]).contains(&WordMatch {
+            word: "XMAS",
+            start: Point { x, y },
             direction,
-            "XMAS"
-        ));
+        }));
+    }
+
+    #[test]
+    fn test_rectangular_grids() {
+        let grid: Grid<char> = "XMASAMX\nMMXSXAM".parse().unwrap();
+
+        let found = find_words(&grid, &["XMAS", "SAM", "MX"])
+            .into_iter()
+            .map(|m| (m.word, m.start.x, m.start.y, m.direction))
+            .collect_vec();
+
+        assert_eq!(
+            found,
+            vec![
+                ("XMAS", 0, 0, Ordinal::East),
+                ("MX", 1, 0, Ordinal::SouthEast),
+                ("MX", 1, 0, Ordinal::West),
+                ("SAM", 3, 0, Ordinal::East),
+                ("SAM", 3, 0, Ordinal::West),
+                ("MX", 5, 0, Ordinal::East),
+                ("MX", 5, 0, Ordinal::SouthWest),
+                ("XMAS", 6, 0, Ordinal::West),
+                ("MX", 0, 1, Ordinal::North),
+                ("MX", 1, 1, Ordinal::East),
+                ("MX", 1, 1, Ordinal::NorthWest),
+                ("MX", 6, 1, Ordinal::North),
+            ]
+        );
+        assert!(find_words(&grid, &["MXX"]).is_empty());
+    }
+
+    #[test]
+    fn test_patterns() {
+        let grid: Grid<char> = "ABCA\nCABC\nBCAB".parse().unwrap();
+
+        let diagonal: Pattern = "A.\n.B".parse().unwrap();
+        assert_eq!(diagonal.rotations.len(), 4);
+        assert_eq!(
+            find_pattern(&grid, &diagonal)
+                .iter()
+                .map(|m| (m.top_left.x, m.top_left.y, m.rotation))
+                .collect_vec(),
+            vec![(2, 0, 1), (0, 1, 1)]
+        );
+
+        let symmetric: Pattern = "B.B\n.A.\nB.B".parse().unwrap();
+        assert_eq!(symmetric.rotations.len(), 1);
+
+        let x_mas: Pattern = X_MAS.parse().unwrap();
+        let found = find_pattern(&parse(SAMPLE).unwrap(), &x_mas);
+        assert_eq!(
+            found[0].points(&x_mas).collect_vec(),
+            vec![
+                Point::new(1, 0),
+                Point::new(3, 0),
+                Point::new(2, 1),
+                Point::new(1, 2),
+                Point::new(3, 2)
+            ]
+        );
     }
 
     #[rstest]
-    #[case::part1(part1, 2571)]
-    #[case::part2(part2, 1992)]
-    fn prod_tests(#[case] f: fn(&Vec<Vec<char>>) -> usize, #[case] expected: usize) {
-        let input = fs::read_to_string("input/2024/day4.txt").unwrap();
-        let parsed = parse(input.trim_end());
-        let result = f(&parsed);
+    #[case::words("highlight_words", Style::Plain)]
+    #[case::words_ansi("highlight_words_ansi", Style::Ansi)]
+    fn test_word_highlights(#[case] name: &str, #[case] style: Style) {
+        let grid = parse(SAMPLE).unwrap();
+        let matches = find_words(&grid, &["XMAS"]);
 
-        assert_eq!(result, expected);
+        insta::assert_snapshot!(
+            name,
+            render_highlights(&grid, matches.iter().map(|m| m.points(&grid)), style)
+        );
+    }
+
+    #[rstest]
+    #[case::pattern("highlight_pattern", Style::Plain)]
+    #[case::pattern_ansi("highlight_pattern_ansi", Style::Ansi)]
+    fn test_pattern_highlights(#[case] name: &str, #[case] style: Style) {
+        let grid = parse(SAMPLE).unwrap();
+        let pattern: Pattern = X_MAS.parse().unwrap();
+        let matches = find_pattern(&grid, &pattern);
+
+        insta::assert_snapshot!(
+            name,
+            render_highlights(&grid, matches.iter().map(|m| m.points(&pattern)), style)
+        );
     }
 }
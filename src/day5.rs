@@ -1,20 +1,27 @@
+use crate::utils::toposort::{toposort, Cycle, Sorted};
 use aoc_runner_derive::{aoc, aoc_generator};
 use color_eyre::eyre::{eyre, Result};
-use itertools::{
-    FoldWhile::{Continue, Done},
-    Itertools,
-};
+use itertools::Itertools;
 use once_cell::sync::Lazy;
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
 
 static COLOR_EYRE: Lazy<()> = Lazy::new(|| color_eyre::install().unwrap());
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct Rule {
     first: u64,
     second: u64,
 }
 
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}|{}", self.first, self.second)
+    }
+}
+
 type Rules = Vec<Rule>;
 type Update = Vec<u64>;
 type Updates = Vec<Update>;
@@ -23,6 +30,191 @@ type Updates = Vec<Update>;
 struct Printing {
     rules: Rules,
     updates: Updates,
+    issues: RuleSetIssues,
+}
+
+impl Printing {
+    fn new(rules: Rules, updates: Updates) -> Self {
+        let issues = RuleSetIssues::analyse(&rules);
+        Self {
+            rules,
+            updates,
+            issues,
+        }
+    }
+}
+
+/// Problems with the rule set as a whole, independent of any update.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct RuleSetIssues {
+    /// Pairs of rules that each demand the other's order, like `a|b` and `b|a`.
+    contradictions: Vec<(Rule, Rule)>,
+    /// A longer chain of rules that loops back on itself, if there is one.
+    cycle: Option<Cycle<u64>>,
+}
+
+impl RuleSetIssues {
+    fn analyse(rules: &Rules) -> Self {
+        let distinct: HashSet<Rule> = rules.iter().copied().collect();
+        let contradictions = rules
+            .iter()
+            .filter(|rule| rule.first < rule.second)
+            .filter_map(|rule| {
+                let reversed = Rule {
+                    first: rule.second,
+                    second: rule.first,
+                };
+                distinct.contains(&reversed).then_some((*rule, reversed))
+            })
+            .unique()
+            .collect();
+
+        let pages: Vec<u64> = rules
+            .iter()
+            .flat_map(|rule| [rule.first, rule.second])
+            .collect();
+        let cycle = toposort(&pages, rules.iter().map(|r| (r.first, r.second))).err();
+
+        Self {
+            contradictions,
+            cycle,
+        }
+    }
+}
+
+/// A rule broken by an update, with the positions at which the update holds its two pages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Violation {
+    rule: Rule,
+    first_position: usize,
+    second_position: usize,
+}
+
+/// Every rule an update breaks, in rule order.
+fn violations(rules: &Rules, update: &Update) -> Vec<Violation> {
+    let positions: HashMap<u64, usize> = update
+        .iter()
+        .enumerate()
+        .map(|(position, page)| (*page, position))
+        .collect();
+
+    rules
+        .iter()
+        .filter_map(|rule| {
+            let first_position = *positions.get(&rule.first)?;
+            let second_position = *positions.get(&rule.second)?;
+
+            (first_position > second_position).then_some(Violation {
+                rule: *rule,
+                first_position,
+                second_position,
+            })
+        })
+        .collect()
+}
+
+/// A printable account of the rule set's issues, of why each update is or is not valid, and of how
+/// each invalid update gets reordered.
+#[derive(Debug)]
+struct Diagnostics<'a> {
+    printing: &'a Printing,
+    violations: Vec<Vec<Violation>>,
+    reorderings: Vec<Option<Result<Sorted<u64>, Cycle<u64>>>>,
+}
+
+impl<'a> Diagnostics<'a> {
+    fn new(printing: &'a Printing) -> Self {
+        let violations = printing
+            .updates
+            .iter()
+            .map(|update| violations(&printing.rules, update))
+            .collect_vec();
+        let reorderings = printing
+            .updates
+            .iter()
+            .zip(&violations)
+            .map(|(update, violations)| {
+                (!violations.is_empty()).then(|| sort(&printing.rules, update))
+            })
+            .collect();
+
+        Self {
+            printing,
+            violations,
+            reorderings,
+        }
+    }
+}
+
+impl fmt::Display for Diagnostics<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let issues = &self.printing.issues;
+
+        writeln!(
+            f,
+            "{} rules, {} updates",
+            self.printing.rules.len(),
+            self.printing.updates.len()
+        )?;
+
+        if issues.contradictions.is_empty() {
+            writeln!(f, "contradictory rules: none")?;
+        } else {
+            writeln!(f, "contradictory rules:")?;
+            for (rule, reversed) in &issues.contradictions {
+                writeln!(f, "  {rule} and {reversed}")?;
+            }
+        }
+
+        match &issues.cycle {
+            Some(cycle) => writeln!(f, "rule {cycle}")?,
+            None => writeln!(f, "rule cycle: none")?,
+        }
+
+        for (number, ((update, violations), reordering)) in self
+            .printing
+            .updates
+            .iter()
+            .zip(&self.violations)
+            .zip(&self.reorderings)
+            .enumerate()
+        {
+            let pages = update.iter().join(",");
+
+            if violations.is_empty() {
+                writeln!(f, "update {} [{pages}]: valid", number + 1)?;
+                continue;
+            }
+
+            writeln!(f, "update {} [{pages}]: invalid", number + 1)?;
+            for violation in violations {
+                writeln!(
+                    f,
+                    "  breaks {}: {} is at position {} but {} is at position {}",
+                    violation.rule,
+                    violation.rule.first,
+                    violation.first_position,
+                    violation.rule.second,
+                    violation.second_position
+                )?;
+            }
+
+            match reordering {
+                Some(Ok(sorted)) if sorted.unique => {
+                    writeln!(f, "  reordered: {}", sorted.order.iter().join(","))?;
+                }
+                Some(Ok(sorted)) => writeln!(
+                    f,
+                    "  reordered: {} (one of several valid orderings)",
+                    sorted.order.iter().join(",")
+                )?,
+                Some(Err(cycle)) => writeln!(f, "  cannot reorder: rule {cycle}")?,
+                None => {}
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[aoc_generator(day5)]
@@ -31,17 +223,23 @@ fn parser(input: &str) -> Result<Printing> {
     parsers::parse_input(input).map_err(|e| eyre!(e.to_string()))
 }
 
+/// Explains which rules each update in `input` breaks, after any contradictions or cycles in the
+/// rule set itself.
+///
+/// # Errors
+///
+/// Fails if the input does not parse.
+pub fn diagnose(input: &str) -> Result<String> {
+    let printing = parser(input)?;
+    Ok(Diagnostics::new(&printing).to_string())
+}
+
 #[aoc(day5, part1)]
 fn part1(printing: &Printing) -> u64 {
-    let rule_map: HashMap<u64, Vec<u64>> = printing
-        .rules
-        .iter()
-        .map(|r| (r.second, r.first))
-        .into_group_map();
     printing
         .updates
         .iter()
-        .filter(|update| is_valid_update(&rule_map, update))
+        .filter(|update| is_valid_update(&printing.rules, update))
         .map(|update| {
             let mid = update.len() / 2;
             update[mid]
@@ -49,70 +247,34 @@ fn part1(printing: &Printing) -> u64 {
         .sum()
 }
 
-fn is_valid_update(rule_map: &HashMap<u64, Vec<u64>>, update: &Update) -> bool {
-    let contents: HashSet<u64> = HashSet::from_iter(update.clone());
-    let (_, valid) = update
-        .iter()
-        .fold_while((HashSet::new(), true), |(mut seen, valid), page| {
-            let preceded_by = rule_map.get(page);
-            if let Some(preceded_by) = preceded_by {
-                if !preceded_by
-                    .iter()
-                    .all(|page| !contents.contains(page) || seen.contains(page))
-                {
-                    return Done((seen, false));
-                }
-            }
-            seen.insert(*page);
-            Continue((seen, valid))
-        })
-        .into_inner();
-    valid
+fn is_valid_update(rules: &Rules, update: &Update) -> bool {
+    violations(rules, update).is_empty()
 }
 
 #[aoc(day5, part2)]
-fn part2(printing: &Printing) -> u64 {
-    let validation_rule_map: HashMap<u64, Vec<u64>> = printing
-        .rules
-        .iter()
-        .map(|r| (r.second, r.first))
-        .into_group_map();
-
-    let bad_updates: Vec<_> = printing
+fn part2(printing: &Printing) -> Result<u64> {
+    printing
         .updates
         .iter()
-        .filter(|update| !is_valid_update(&validation_rule_map, update))
-        .collect();
-
-    let sort_rule_map: HashMap<u64, Vec<u64>> = printing
-        .rules
-        .iter()
-        .map(|r| (r.first, r.second))
-        .into_group_map();
-
-    bad_updates
-        .iter()
-        .map(|update| {
-            update
-                .iter()
-                .sorted_by(|a, b| {
-                    if let Some(after) = sort_rule_map.get(a) {
-                        if after.contains(b) {
-                            return std::cmp::Ordering::Less;
-                        };
-                    };
-
-                    std::cmp::Ordering::Equal
-                })
-                .collect::<Vec<_>>()
-        })
+        .filter(|update| !is_valid_update(&printing.rules, update))
         .map(|update| {
+            let update = reorder(&printing.rules, update)?.order;
             let mid = update.len() / 2;
-            update[mid]
+            Ok(update[mid])
         })
         .sum()
 }
 
+/// Reorders the pages of `update` so that every rule between them is satisfied. When the rules
+/// allow more than one such order, [`Sorted::unique`] is false and [`diagnose`] says so.
+fn reorder(rules: &Rules, update: &Update) -> Result<Sorted<u64>> {
+    sort(rules, update).map_err(|cycle| eyre!("the rules for update {update:?} contain a {cycle}"))
+}
+
+fn sort(rules: &Rules, update: &Update) -> Result<Sorted<u64>, Cycle<u64>> {
+    toposort(update, rules.iter().map(|r| (r.first, r.second)))
+}
+
 mod parsers {
     use crate::day5::{Printing, Rule, Rules, Update, Updates};
     use nom::{
@@ -130,14 +292,14 @@ mod parsers {
 
     type Span<'a> = LocatedSpan<&'a str>;
 
-    pub(super) fn parse_input(input: &str) -> color_eyre::Result<Printing, ParseError> {
+    pub(super) fn parse_input(input: &str) -> color_eyre::Result<Printing, ParseError<'_>> {
         final_parser(printing)(Span::new(input))
     }
 
     fn printing(input: Span) -> IResult<Span, Printing, ParseError> {
         rules
             .and(updates.preceded_by(many1(newline)))
-            .map(|(rules, updates)| Printing { rules, updates })
+            .map(|(rules, updates)| Printing::new(rules, updates))
             .parse(input)
     }
 
@@ -166,7 +328,6 @@ mod tests {
     use indoc::indoc;
     use pretty_assertions::assert_eq;
     use rstest::rstest;
-    use std::fs;
 
     const SAMPLE: &str = indoc! {
         "47|53
@@ -206,23 +367,102 @@ mod tests {
     }
 
     #[rstest]
-    #[case::part1(part1, 143)]
+    #[case::part1(|p: &Printing| Ok(part1(p)), 143)]
     #[case::part2(part2, 123)]
-    fn sample_tests(#[case] f: fn(&Printing) -> u64, #[case] expected: u64) {
+    fn sample_tests(#[case] f: fn(&Printing) -> Result<u64>, #[case] expected: u64) {
         let parsed = parser(SAMPLE).unwrap();
-        let result = f(&parsed);
+        let result = f(&parsed).unwrap();
 
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_violations() {
+        let parsed = parser(SAMPLE).unwrap();
+
+        let counts = parsed
+            .updates
+            .iter()
+            .map(|update| violations(&parsed.rules, update).len())
+            .collect_vec();
+        assert_eq!(counts, vec![0, 0, 0, 1, 1, 4]);
+
+        assert_eq!(
+            violations(&parsed.rules, &parsed.updates[4]),
+            vec![Violation {
+                rule: Rule {
+                    first: 29,
+                    second: 13
+                },
+                first_position: 2,
+                second_position: 1,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let parsed = parser(SAMPLE).unwrap();
+        insta::assert_snapshot!(Diagnostics::new(&parsed).to_string());
+    }
+
+    #[test]
+    fn test_rule_set_issues() {
+        let parsed = parser("1|2\n2|1\n2|3\n3|4\n4|2\n2|1\n\n1,2").unwrap();
+
+        assert_eq!(
+            parsed.issues.contradictions,
+            vec![(
+                Rule {
+                    first: 1,
+                    second: 2
+                },
+                Rule {
+                    first: 2,
+                    second: 1
+                }
+            )]
+        );
+        assert!(parsed.issues.cycle.is_some());
+        insta::assert_snapshot!(Diagnostics::new(&parsed).to_string());
+    }
+
     #[rstest]
-    #[case::part1(part1, 4905)]
-    #[case::part2(part2, 6204)]
-    fn prod_tests(#[case] f: fn(&Printing) -> u64, #[case] expected: u64) {
-        let input = fs::read_to_string("input/2024/day5.txt").unwrap();
-        let parsed = parser(input.trim_end()).unwrap();
-        let result = f(&parsed);
+    #[case(vec![75, 97, 47, 61, 53], vec![97, 75, 47, 61, 53])]
+    #[case(vec![61, 13, 29], vec![61, 29, 13])]
+    #[case(vec![97, 13, 75, 29, 47], vec![97, 75, 47, 29, 13])]
+    fn reordering(#[case] update: Update, #[case] expected: Update) {
+        let parsed = parser(SAMPLE).unwrap();
 
-        assert_eq!(result, expected);
+        let sorted = reorder(&parsed.rules, &update).unwrap();
+        assert_eq!(sorted.order, expected);
+        assert!(sorted.unique);
+    }
+
+    #[test]
+    fn ambiguous_reordering() {
+        let parsed = parser("1|3\n2|3\n\n3,1,2").unwrap();
+
+        let sorted = reorder(&parsed.rules, &parsed.updates[0]).unwrap();
+        assert_eq!(sorted.order, vec![1, 2, 3]);
+        assert!(!sorted.unique);
+        assert!(Diagnostics::new(&parsed)
+            .to_string()
+            .contains("  reordered: 1,2,3 (one of several valid orderings)\n"));
+    }
+
+    #[test]
+    fn cyclic_rules() {
+        let parsed = parser("1|2\n2|3\n3|1\n\n1,2,3\n3,2").unwrap();
+
+        let err = part2(&parsed).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the rules for update [1, 2, 3] contain a cycle 1 -> 2 -> 3 -> 1"
+        );
+        assert_eq!(
+            reorder(&parsed.rules, &vec![3, 2]).unwrap().order,
+            vec![2, 3]
+        );
     }
 }
@@ -1,29 +1,37 @@
-use crate::utils::point::Point;
+use crate::utils::{direction::Cardinal, grid, point::Point};
 use aoc_runner_derive::{aoc, aoc_generator};
 use color_eyre::eyre::{eyre, Result};
 use itertools::Itertools;
 use once_cell::sync::Lazy;
-use std::{collections::HashSet, hash::Hash};
+use rayon::prelude::*;
+use std::{collections::HashSet, fmt, io::Write, thread, time::Duration};
 
 static COLOR_EYRE: Lazy<()> = Lazy::new(|| color_eyre::install().unwrap());
 
-#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 enum Tile {
     Space,
     Obstacle,
-    Guard(Direction),
+    Guard(Cardinal),
+}
+
+impl Tile {
+    fn glyph(self) -> char {
+        match self {
+            Tile::Space => '.',
+            Tile::Obstacle => '#',
+            Tile::Guard(dir) => dir.glyph(),
+        }
+    }
+}
+
+impl fmt::Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.glyph())
+    }
 }
 
-type GridRow = Vec<Tile>;
-type Grid = Vec<GridRow>;
+type Grid = grid::Grid<Tile>;
 
 #[aoc_generator(day6)]
 fn parser(input: &str) -> Result<Grid> {
@@ -31,324 +39,374 @@ fn parser(input: &str) -> Result<Grid> {
     parsers::parse_input(input).map_err(|e| eyre!(e.to_string()))
 }
 
-#[aoc(day6, part1)]
-fn part1(grid: &Grid) -> usize {
-    let mut grid = grid.clone();
-    let grid_size = grid.len() as i64;
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+enum Outcome {
+    Exited,
+    Looped,
+}
 
-    let mut seen: HashSet<_> = HashSet::new();
+type State = (Point<usize>, Cardinal);
+
+/// Walks the guard's patrol one state at a time.
+///
+/// Each item is the guard's position and heading; turning in place yields a new state at the
+/// same position. Iteration ends when the guard steps off the map or revisits a state.
+#[derive(Debug, Clone)]
+struct GuardSim<'a> {
+    grid: &'a Grid,
+    extra_obstacle: Option<Point<usize>>,
+    next_state: Option<State>,
+    seen: HashSet<State>,
+    outcome: Option<Outcome>,
+}
 
-    let (y, row) = grid
-        .iter()
-        .find_position(|row| row.contains(&Tile::Guard(Direction::Up)))
-        .unwrap();
+impl<'a> GuardSim<'a> {
+    fn new(grid: &'a Grid) -> Option<Self> {
+        let start = grid.find(|tile| matches!(tile, Tile::Guard(_)))?;
+        let Tile::Guard(heading) = grid[start] else {
+            unreachable!()
+        };
 
-    let (x, _) = row
-        .iter()
-        .find_position(|tile| *tile == &Tile::Guard(Direction::Up))
-        .unwrap();
+        Some(Self {
+            grid,
+            extra_obstacle: None,
+            next_state: Some((start, heading)),
+            seen: HashSet::new(),
+            outcome: None,
+        })
+    }
 
-    let mut guard_pos: Point<i64> = Point {
-        x: x as i64,
-        y: y as i64,
-    };
+    /// Simulates the patrol as if `obstacle` were also blocking the map, without touching the grid.
+    fn with_obstacle(grid: &'a Grid, obstacle: Point<usize>) -> Option<Self> {
+        Some(Self {
+            extra_obstacle: Some(obstacle),
+            ..Self::new(grid)?
+        })
+    }
 
-    while guard_pos.x < grid_size && guard_pos.y < grid_size && guard_pos.x >= 0 && guard_pos.y >= 0
-    {
-        seen.insert(guard_pos);
-        let guard = grid[guard_pos.y as usize][guard_pos.x as usize];
+    fn start(&self) -> Option<State> {
+        self.next_state
+    }
 
-        let Tile::Guard(mut dir) = guard else {
-            unreachable!()
-        };
+    /// How the patrol ended, once the iterator has been exhausted.
+    fn outcome(&self) -> Option<Outcome> {
+        self.outcome
+    }
 
-        let step_forward = match dir {
-            Direction::Up => Point {
-                y: guard_pos.y - 1,
-                ..guard_pos
-            },
-            Direction::Down => Point {
-                y: guard_pos.y + 1,
-                ..guard_pos
-            },
-            Direction::Left => Point {
-                x: guard_pos.x - 1,
-                ..guard_pos
-            },
-            Direction::Right => Point {
-                x: guard_pos.x + 1,
-                ..guard_pos
-            },
-        };
+    fn run(mut self) -> Outcome {
+        self.by_ref().for_each(drop);
+        self.outcome()
+            .expect("an exhausted simulation always has an outcome")
+    }
 
-        let step_forward = if step_forward.x > grid_size
-            || step_forward.y > grid_size
-            || step_forward.x < 0
-            || step_forward.y < 0
-        {
-            continue;
-        } else {
-            Point::new(step_forward.x as usize, step_forward.y as usize)
-        };
+    fn is_blocked(&self, pos: Point<usize>) -> bool {
+        self.extra_obstacle == Some(pos) || self.grid[pos] == Tile::Obstacle
+    }
+}
 
-        let next_pos = if let Some(Tile::Obstacle) = grid
-            .get(step_forward.y)
-            .and_then(|row| row.get(step_forward.x))
-        {
-            dir = match dir {
-                Direction::Up => Direction::Right,
-                Direction::Down => Direction::Left,
-                Direction::Left => Direction::Up,
-                Direction::Right => Direction::Down,
-            };
-
-            guard_pos
-        } else {
-            Point::new(step_forward.x as i64, step_forward.y as i64)
-        };
+impl Iterator for GuardSim<'_> {
+    type Item = State;
 
-        grid[guard_pos.y as usize][guard_pos.x as usize] = Tile::Space;
+    fn next(&mut self) -> Option<Self::Item> {
+        let current @ (pos, heading) = self.next_state?;
 
-        if let (Ok(x), Ok(y)) = (usize::try_from(next_pos.x), usize::try_from(next_pos.y)) {
-            if let Some(row) = grid.get_mut(y) {
-                if let Some(tile) = row.get_mut(x) {
-                    *tile = Tile::Guard(dir);
-                }
-            }
+        if !self.seen.insert(current) {
+            self.outcome = Some(Outcome::Looped);
+            self.next_state = None;
+            return None;
         }
 
-        guard_pos = next_pos;
+        self.next_state = match self.grid.step(pos, heading.to_offset()) {
+            None => {
+                self.outcome = Some(Outcome::Exited);
+                None
+            }
+            Some(ahead) if self.is_blocked(ahead) => Some((pos, heading.turn_right())),
+            Some(ahead) => Some((ahead, heading)),
+        };
+
+        Some(current)
     }
+}
 
-    seen.len()
+#[aoc(day6, part1)]
+fn part1(grid: &Grid) -> usize {
+    patrol_path(grid).len()
 }
 
 #[aoc(day6, part2)]
 fn part2(grid: &Grid) -> usize {
-    let (y, row) = grid
-        .iter()
-        .find_position(|row| row.contains(&Tile::Guard(Direction::Up)))
-        .unwrap();
-
-    let (x, _) = row
-        .iter()
-        .find_position(|tile| *tile == &Tile::Guard(Direction::Up))
-        .unwrap();
-
-    let guard_pos: Point<i64> = Point {
-        x: x as i64,
-        y: y as i64,
-    };
+    let table = JumpTable::new(grid);
+
+    obstacle_candidates(grid)
+        .into_par_iter()
+        .map_init(
+            || Visited::new(grid),
+            |visited, (obstacle, start)| {
+                visited.clear();
+                table.loops_with(start, obstacle, visited)
+            },
+        )
+        .filter(|looped| *looped)
+        .count()
+}
 
-    let possible_blocks = grid_run(grid);
+/// The original step by step simulation, kept as a reference for the jump table solution.
+#[aoc(day6, part2, Simulated)]
+fn part2_simulated(grid: &Grid) -> usize {
+    let (start, _) = GuardSim::new(grid).and_then(|sim| sim.start()).unwrap();
 
-    possible_blocks
-        .iter()
-        .filter(|block| **block != guard_pos)
-        .map(|block| {
-            let mut grid = grid.clone();
-            grid[block.y as usize][block.x as usize] = Tile::Obstacle;
-            grid
-        })
-        .filter(has_cycle)
+    patrol_path(grid)
+        .into_iter()
+        .filter(|block| *block != start)
+        .filter(|block| GuardSim::with_obstacle(grid, *block).unwrap().run() == Outcome::Looped)
         .count()
 }
 
-fn grid_run(grid: &Grid) -> HashSet<Point<i64>> {
-    let mut grid = grid.clone();
-    let grid_size = grid.len() as i64;
-
-    let mut seen: HashSet<_> = HashSet::new();
+/// Every cell on the patrol where an obstacle could go, paired with the state the guard is in
+/// just before first reaching it.
+///
+/// The route up to that state never touches the cell, so the simulation can resume from there
+/// rather than from the guard's starting position.
+fn obstacle_candidates(grid: &Grid) -> Vec<(Point<usize>, State)> {
+    let sim = GuardSim::new(grid).unwrap();
+    let (start, _) = sim.start().unwrap();
+    let mut seen = HashSet::from([start]);
+
+    sim.tuple_windows()
+        .filter_map(|(before, (pos, _))| seen.insert(pos).then_some((pos, before)))
+        .collect()
+}
 
-    let (y, row) = grid
-        .iter()
-        .find_position(|row| row.contains(&Tile::Guard(Direction::Up)))
-        .unwrap();
+/// For every cell and heading, the cell the guard stops on before the next obstacle, or `None`
+/// when the guard would walk off the map.
+struct JumpTable {
+    width: usize,
+    stops: Vec<Option<Point<usize>>>,
+}
 
-    let (x, _) = row
-        .iter()
-        .find_position(|tile| *tile == &Tile::Guard(Direction::Up))
-        .unwrap();
+impl JumpTable {
+    fn new(grid: &Grid) -> Self {
+        let (width, height) = (grid.width(), grid.height());
+        let mut stops = vec![None; width * height * 4];
+
+        let mut scan = |heading: Cardinal, line: &mut dyn Iterator<Item = Point<usize>>| {
+            let mut stop = None;
+            for pos in line {
+                if grid[pos] == Tile::Obstacle {
+                    stop = grid.step(pos, heading.reverse().to_offset());
+                } else {
+                    stops[Self::index(width, pos, heading)] = stop;
+                }
+            }
+        };
 
-    let mut guard_pos: Point<i64> = Point {
-        x: x as i64,
-        y: y as i64,
-    };
+        for x in 0..width {
+            scan(Cardinal::North, &mut (0..height).map(|y| Point::new(x, y)));
+            scan(
+                Cardinal::South,
+                &mut (0..height).rev().map(|y| Point::new(x, y)),
+            );
+        }
 
-    while guard_pos.x < grid_size && guard_pos.y < grid_size && guard_pos.x >= 0 && guard_pos.y >= 0
-    {
-        seen.insert(guard_pos);
-        let guard = grid[guard_pos.y as usize][guard_pos.x as usize];
+        for y in 0..height {
+            scan(Cardinal::West, &mut (0..width).map(|x| Point::new(x, y)));
+            scan(
+                Cardinal::East,
+                &mut (0..width).rev().map(|x| Point::new(x, y)),
+            );
+        }
 
-        let Tile::Guard(mut dir) = guard else {
-            unreachable!()
-        };
+        Self { width, stops }
+    }
 
-        let step_forward = match dir {
-            Direction::Up => Point {
-                y: guard_pos.y - 1,
-                ..guard_pos
-            },
-            Direction::Down => Point {
-                y: guard_pos.y + 1,
-                ..guard_pos
-            },
-            Direction::Left => Point {
-                x: guard_pos.x - 1,
-                ..guard_pos
-            },
-            Direction::Right => Point {
-                x: guard_pos.x + 1,
-                ..guard_pos
-            },
-        };
+    fn index(width: usize, pos: Point<usize>, heading: Cardinal) -> usize {
+        (pos.y * width + pos.x) * 4 + heading as usize
+    }
 
-        let step_forward = if step_forward.x > grid_size
-            || step_forward.y > grid_size
-            || step_forward.x < 0
-            || step_forward.y < 0
-        {
-            continue;
-        } else {
-            Point::new(step_forward.x as usize, step_forward.y as usize)
+    /// Where the guard stops when walking from `pos`, accounting for one added obstacle.
+    fn stop(
+        &self,
+        pos: Point<usize>,
+        heading: Cardinal,
+        obstacle: Point<usize>,
+    ) -> Option<Point<usize>> {
+        let stop = self.stops[Self::index(self.width, pos, heading)];
+
+        let Some(to_obstacle) = steps_to(pos, heading, obstacle) else {
+            return stop;
         };
 
-        let next_pos = if let Some(Tile::Obstacle) = grid
-            .get(step_forward.y)
-            .and_then(|row| row.get(step_forward.x))
-        {
-            dir = match dir {
-                Direction::Up => Direction::Right,
-                Direction::Down => Direction::Left,
-                Direction::Left => Direction::Up,
-                Direction::Right => Direction::Down,
-            };
-
-            guard_pos
-        } else {
-            Point::new(step_forward.x as i64, step_forward.y as i64)
-        };
+        match stop {
+            Some(stop) if steps_to(pos, heading, stop).unwrap_or(0) < to_obstacle => Some(stop),
+            _ => Some(advance(pos, heading, to_obstacle - 1)),
+        }
+    }
 
-        grid[guard_pos.y as usize][guard_pos.x as usize] = Tile::Space;
+    fn loops_with(&self, start: State, obstacle: Point<usize>, visited: &mut Visited) -> bool {
+        let (mut pos, mut heading) = start;
 
-        if let (Ok(x), Ok(y)) = (usize::try_from(next_pos.x), usize::try_from(next_pos.y)) {
-            if let Some(row) = grid.get_mut(y) {
-                if let Some(tile) = row.get_mut(x) {
-                    *tile = Tile::Guard(dir);
-                }
+        while let Some(stop) = self.stop(pos, heading, obstacle) {
+            if !visited.insert(Self::index(self.width, stop, heading)) {
+                return true;
             }
+
+            pos = stop;
+            heading = heading.turn_right();
         }
 
-        guard_pos = next_pos;
+        false
     }
-
-    seen
 }
 
-fn has_cycle(grid: &Grid) -> bool {
-    let mut grid = grid.clone();
-    let grid_size = grid.len() as i64;
-
-    let mut seen: HashSet<_> = HashSet::new();
+/// How many steps along `heading` it takes to get from `from` to `to`, if `to` is ahead.
+fn steps_to(from: Point<usize>, heading: Cardinal, to: Point<usize>) -> Option<usize> {
+    match heading {
+        Cardinal::North if from.x == to.x && to.y < from.y => Some(from.y - to.y),
+        Cardinal::South if from.x == to.x && to.y > from.y => Some(to.y - from.y),
+        Cardinal::West if from.y == to.y && to.x < from.x => Some(from.x - to.x),
+        Cardinal::East if from.y == to.y && to.x > from.x => Some(to.x - from.x),
+        _ => None,
+    }
+}
 
-    let (y, row) = grid
-        .iter()
-        .find_position(|row| row.contains(&Tile::Guard(Direction::Up)))
-        .unwrap();
+fn advance(pos: Point<usize>, heading: Cardinal, steps: usize) -> Point<usize> {
+    match heading {
+        Cardinal::North => Point::new(pos.x, pos.y - steps),
+        Cardinal::South => Point::new(pos.x, pos.y + steps),
+        Cardinal::West => Point::new(pos.x - steps, pos.y),
+        Cardinal::East => Point::new(pos.x + steps, pos.y),
+    }
+}
 
-    let (x, _) = row
-        .iter()
-        .find_position(|tile| *tile == &Tile::Guard(Direction::Up))
-        .unwrap();
+/// A dense bitset over every (cell, heading) state of a grid.
+struct Visited(Vec<u64>);
 
-    let mut guard_pos: Point<i64> = Point {
-        x: x as i64,
-        y: y as i64,
-    };
+impl Visited {
+    fn new(grid: &Grid) -> Self {
+        Self(vec![0; (grid.width() * grid.height() * 4).div_ceil(64)])
+    }
 
-    while guard_pos.x < grid_size && guard_pos.y < grid_size && guard_pos.x >= 0 && guard_pos.y >= 0
-    {
-        let guard = grid[guard_pos.y as usize][guard_pos.x as usize];
-        let Tile::Guard(mut dir) = guard else {
-            unreachable!()
-        };
+    fn clear(&mut self) {
+        self.0.fill(0);
+    }
 
-        if seen.contains(&(guard_pos, dir)) {
-            return true;
-        }
-        seen.insert((guard_pos, dir));
+    /// Marks `index` as visited, returning whether it was previously unvisited.
+    fn insert(&mut self, index: usize) -> bool {
+        let (word, bit) = (index / 64, 1 << (index % 64));
+        let fresh = self.0[word] & bit == 0;
+        self.0[word] |= bit;
+        fresh
+    }
+}
 
-        let step_forward = match dir {
-            Direction::Up => Point {
-                y: guard_pos.y - 1,
-                ..guard_pos
-            },
-            Direction::Down => Point {
-                y: guard_pos.y + 1,
-                ..guard_pos
-            },
-            Direction::Left => Point {
-                x: guard_pos.x - 1,
-                ..guard_pos
-            },
-            Direction::Right => Point {
-                x: guard_pos.x + 1,
-                ..guard_pos
-            },
-        };
+fn patrol_path(grid: &Grid) -> HashSet<Point<usize>> {
+    GuardSim::new(grid).unwrap().map(|(pos, _)| pos).collect()
+}
 
-        let step_forward = if step_forward.x > grid_size
-            || step_forward.y > grid_size
-            || step_forward.x < 0
-            || step_forward.y < 0
-        {
-            guard_pos = step_forward;
-            continue;
-        } else {
-            Point::new(step_forward.x as usize, step_forward.y as usize)
-        };
+/// Draws the guard's patrol over the map in the puzzle's notation: `|` and `-` for vertical and
+/// horizontal movement, `+` where the two cross, and `O` for the added obstacle, if any.
+///
+/// # Errors
+///
+/// Fails if the map does not parse, or if `obstacle` is not an empty cell of the map.
+pub fn patrol_overlay(input: &str, obstacle: Option<Point<usize>>) -> Result<String> {
+    let grid = parser(input)?;
+    let states = patrol_states(&grid, obstacle)?;
+
+    Ok(overlay(&grid, &states, obstacle).to_string())
+}
 
-        let next_pos = if let Some(Tile::Obstacle) = grid
-            .get(step_forward.y)
-            .and_then(|row| row.get(step_forward.x))
-        {
-            dir = match dir {
-                Direction::Up => Direction::Right,
-                Direction::Down => Direction::Left,
-                Direction::Left => Direction::Up,
-                Direction::Right => Direction::Down,
-            };
-
-            guard_pos
-        } else {
-            Point::new(step_forward.x as i64, step_forward.y as i64)
-        };
+/// Writes the patrol to `out` one frame per step, redrawing the terminal between frames.
+///
+/// # Errors
+///
+/// Fails if the map does not parse, `obstacle` is not an empty cell, or writing to `out` fails.
+pub fn animate_patrol<W: Write>(
+    input: &str,
+    obstacle: Option<Point<usize>>,
+    delay: Duration,
+    out: &mut W,
+) -> Result<()> {
+    const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+    let grid = parser(input)?;
+    let states = patrol_states(&grid, obstacle)?;
+
+    for (step, (pos, heading)) in states.iter().enumerate() {
+        let mut frame = overlay(&grid, &states[..=step], obstacle);
+        frame[*pos] = heading.glyph();
+
+        write!(
+            out,
+            "{CLEAR_SCREEN}{frame}\n\nstep {}/{}\n",
+            step + 1,
+            states.len()
+        )?;
+        out.flush()?;
+        thread::sleep(delay);
+    }
 
-        grid[guard_pos.y as usize][guard_pos.x as usize] = Tile::Space;
+    Ok(())
+}
 
-        if let (Ok(x), Ok(y)) = (usize::try_from(next_pos.x), usize::try_from(next_pos.y)) {
-            if let Some(row) = grid.get_mut(y) {
-                if let Some(tile) = row.get_mut(x) {
-                    *tile = Tile::Guard(dir);
+fn patrol_states(grid: &Grid, obstacle: Option<Point<usize>>) -> Result<Vec<State>> {
+    let sim = match obstacle {
+        Some(obstacle) => {
+            match grid.get(obstacle) {
+                Some(Tile::Space) => {}
+                Some(tile) => {
+                    return Err(eyre!("cannot place an obstacle on {tile} at {obstacle:?}"))
                 }
+                None => return Err(eyre!("{obstacle:?} is outside the map")),
             }
+            GuardSim::with_obstacle(grid, obstacle)
         }
+        None => GuardSim::new(grid),
+    };
 
-        guard_pos = next_pos;
+    Ok(sim.expect("parsed maps always have a guard").collect())
+}
+
+fn overlay(grid: &Grid, states: &[State], obstacle: Option<Point<usize>>) -> grid::Grid<char> {
+    let mut vertical = grid::Grid::new(grid.width(), grid.height(), false);
+    let mut horizontal = vertical.clone();
+
+    for (pos, heading) in states {
+        match heading {
+            Cardinal::North | Cardinal::South => vertical[*pos] = true,
+            Cardinal::East | Cardinal::West => horizontal[*pos] = true,
+        }
     }
 
-    false
+    let mut drawn = grid.map(|tile| tile.glyph());
+    for pos in grid.positions(|tile| *tile == Tile::Space) {
+        drawn[pos] = match (vertical[pos], horizontal[pos]) {
+            (true, true) => '+',
+            (true, false) => '|',
+            (false, true) => '-',
+            (false, false) => '.',
+        };
+    }
+
+    if let Some(obstacle) = obstacle {
+        drawn[obstacle] = 'O';
+    }
+
+    drawn
 }
 
 mod parsers {
-    use crate::day6::{Direction, Grid, GridRow, Tile};
+    use crate::{
+        day6::{Grid, Tile},
+        utils::{direction::Cardinal, grid::parsers::grid as grid_of},
+    };
     use nom::{
         branch::alt,
-        character::complete::newline,
-        multi::{many1, separated_list1},
+        error::{ErrorKind, FromExternalError},
         IResult, Parser,
     };
-    use nom_locate::LocatedSpan;
+    use nom_locate::{position, LocatedSpan};
     use nom_supreme::{
         error::ErrorTree, final_parser::final_parser, tag::complete::tag, ParserExt,
     };
@@ -357,16 +415,53 @@ mod parsers {
 
     type Span<'a> = LocatedSpan<&'a str>;
 
-    pub(super) fn parse_input(input: &str) -> color_eyre::Result<Grid, ParseError> {
+    pub(super) fn parse_input(input: &str) -> color_eyre::Result<Grid, ParseError<'_>> {
         final_parser(grid)(Span::new(input))
     }
 
     fn grid(input: Span) -> IResult<Span, Grid, ParseError> {
-        separated_list1(newline, grid_row).parse(input)
+        let (rest, located) = grid_of(position.and(tile)).parse(input)?;
+
+        let guards: Vec<Span> = located
+            .iter()
+            .filter(|(_, (_, tile))| matches!(tile, Tile::Guard(_)))
+            .map(|(_, (span, _))| *span)
+            .collect();
+
+        match guards.as_slice() {
+            [_] => Ok((rest, located.map(|(_, tile)| *tile))),
+            [] => Err(guard_error(
+                rest,
+                format!(
+                    "no guard found in the map, which ends at {}",
+                    line_column(rest)
+                ),
+            )),
+            [first, second, ..] => Err(guard_error(
+                *second,
+                format!(
+                    "found a second guard at {}, but the map already has one at {}",
+                    line_column(*second),
+                    line_column(*first)
+                ),
+            )),
+        }
+    }
+
+    fn guard_error(location: Span, message: String) -> nom::Err<ParseError> {
+        nom::Err::Failure(ParseError::from_external_error(
+            location,
+            ErrorKind::Verify,
+            message,
+        ))
     }
 
-    fn grid_row(input: Span) -> IResult<Span, GridRow, ParseError> {
-        many1(tile).context("grid row").parse(input)
+    fn line_column(span: Span) -> String {
+        format!(
+            "line {}, column {}",
+            span.location_line(),
+            span.get_utf8_column()
+        )
     }
 
     fn tile(input: Span) -> IResult<Span, Tile, ParseError> {
@@ -384,7 +479,15 @@ mod parsers {
     }
 
     fn guard(input: Span) -> IResult<Span, Tile, ParseError> {
-        tag("^").map(|_| Tile::Guard(Direction::Up)).parse(input)
+        alt((
+            tag("^").value(Cardinal::North),
+            tag(">").value(Cardinal::East),
+            tag("v").value(Cardinal::South),
+            tag("<").value(Cardinal::West),
+        ))
+        .map(Tile::Guard)
+        .context("guard")
+        .parse(input)
     }
 }
 
@@ -394,7 +497,6 @@ mod tests {
     use indoc::indoc;
     use pretty_assertions::assert_eq;
     use rstest::rstest;
-    use std::fs;
 
     const SAMPLE: &str = indoc! {
         "....#.....
@@ -415,22 +517,145 @@ mod tests {
         insta::assert_debug_snapshot!(reports);
     }
 
+    #[test]
+    fn test_rendering() {
+        let grid = parser(SAMPLE).unwrap();
+        assert_eq!(grid.to_string(), SAMPLE);
+    }
+
     #[rstest]
-    #[case::part1(part1, 41)]
-    #[case::part2(part2, 6)]
-    fn sample_tests(#[case] f: fn(&Grid) -> usize, #[case] expected: usize) {
-        let parsed = parser(SAMPLE).unwrap();
-        let result = f(&parsed);
+    #[case::north("..\n^.", Cardinal::North)]
+    #[case::east("..\n>.", Cardinal::East)]
+    #[case::south("..\nv.", Cardinal::South)]
+    #[case::west("..\n<.", Cardinal::West)]
+    fn test_guard_headings(#[case] input: &str, #[case] heading: Cardinal) {
+        let grid = parser(input).unwrap();
+
+        assert_eq!(grid[Point::new(0, 1)], Tile::Guard(heading));
+        assert_eq!(
+            GuardSim::new(&grid).unwrap().start(),
+            Some((Point::new(0, 1), heading))
+        );
+    }
 
-        assert_eq!(result, expected);
+    #[test]
+    fn test_guard_count_errors() {
+        let missing = parser("...\n.#.").unwrap_err().to_string();
+        assert!(
+            missing.contains("no guard found in the map, which ends at line 2, column 4"),
+            "{missing}"
+        );
+
+        let extra = parser("..^\n.#.\n<..").unwrap_err().to_string();
+        assert!(
+            extra.contains(
+                "found a second guard at line 3, column 1, but the map already has one at line 1, column 3"
+            ),
+            "{extra}"
+        );
+    }
+
+    #[test]
+    fn test_guard_sim() {
+        let grid = parser(SAMPLE).unwrap();
+
+        let mut sim = GuardSim::new(&grid).unwrap();
+        assert_eq!(sim.start(), Some((Point::new(4, 6), Cardinal::North)));
+        assert_eq!(sim.next(), Some((Point::new(4, 6), Cardinal::North)));
+        assert_eq!(sim.nth(5), Some((Point::new(4, 1), Cardinal::East)));
+        assert_eq!(sim.outcome(), None);
+        assert_eq!(sim.run(), Outcome::Exited);
+
+        let sim = GuardSim::with_obstacle(&grid, Point::new(3, 6)).unwrap();
+        assert_eq!(sim.run(), Outcome::Looped);
+        assert_eq!(grid[Point::new(3, 6)], Tile::Space);
+    }
+
+    #[test]
+    fn test_jump_table() {
+        let grid = parser(SAMPLE).unwrap();
+        let table = JumpTable::new(&grid);
+        let nowhere = Point::new(usize::MAX, usize::MAX);
+
+        assert_eq!(
+            table.stop(Point::new(4, 6), Cardinal::North, nowhere),
+            Some(Point::new(4, 1))
+        );
+        assert_eq!(
+            table.stop(Point::new(4, 6), Cardinal::North, Point::new(4, 3)),
+            Some(Point::new(4, 4))
+        );
+        assert_eq!(
+            table.stop(Point::new(4, 6), Cardinal::North, Point::new(4, 0)),
+            Some(Point::new(4, 1))
+        );
+        assert_eq!(table.stop(Point::new(4, 6), Cardinal::South, nowhere), None);
+        assert_eq!(
+            table.stop(Point::new(4, 6), Cardinal::South, Point::new(4, 8)),
+            Some(Point::new(4, 7))
+        );
+        assert_eq!(
+            table.stop(Point::new(4, 6), Cardinal::West, nowhere),
+            Some(Point::new(2, 6))
+        );
+        assert_eq!(
+            table.stop(Point::new(8, 1), Cardinal::East, nowhere),
+            Some(Point::new(8, 1))
+        );
+    }
+
+    #[test]
+    fn test_patrol_overlay() {
+        insta::assert_snapshot!(patrol_overlay(SAMPLE, None).unwrap());
+
+        let looped = patrol_overlay(SAMPLE, Some(Point::new(3, 6))).unwrap();
+        assert_eq!(
+            looped,
+            indoc! {
+                "....#.....
+                 ....+---+#
+                 ....|...|.
+                 ..#.|...|.
+                 ....|..#|.
+                 ....|...|.
+                 .#.O^---+.
+                 ........#.
+                 #.........
+                 ......#..."
+            }
+        );
+
+        assert!(patrol_overlay(SAMPLE, Some(Point::new(4, 0))).is_err());
+        assert!(patrol_overlay(SAMPLE, Some(Point::new(4, 6))).is_err());
+        assert!(patrol_overlay(SAMPLE, Some(Point::new(10, 0))).is_err());
+    }
+
+    #[test]
+    fn test_patrol_animation() {
+        let mut out = Vec::new();
+        animate_patrol(SAMPLE, Some(Point::new(3, 6)), Duration::ZERO, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        let frames: Vec<_> = out.split("\x1b[2J\x1b[H").skip(1).collect();
+        let states = GuardSim::with_obstacle(&parser(SAMPLE).unwrap(), Point::new(3, 6))
+            .unwrap()
+            .count();
+        assert_eq!(frames.len(), states);
+        assert!(frames[1].starts_with("....#.....\n.........#"));
+        assert!(frames[1].contains("\n....^.....\n"));
+        assert!(frames[1].contains("\n.#.O^.....\n"));
+        assert!(frames
+            .last()
+            .unwrap()
+            .ends_with(&format!("step {states}/{states}\n")));
     }
 
     #[rstest]
-    #[case::part1(part1, 5329)]
-    #[case::part2(part2, 2162)]
-    fn prod_tests(#[case] f: fn(&Grid) -> usize, #[case] expected: usize) {
-        let input = fs::read_to_string("input/2024/day6.txt").unwrap();
-        let parsed = parser(input.trim_end()).unwrap();
+    #[case::part1(part1, 41)]
+    #[case::part2(part2, 6)]
+    #[case::part2_simulated(part2_simulated, 6)]
+    fn sample_tests(#[case] f: fn(&Grid) -> usize, #[case] expected: usize) {
+        let parsed = parser(SAMPLE).unwrap();
         let result = f(&parsed);
 
         assert_eq!(result, expected);
@@ -1,6 +1,12 @@
 use aoc_runner_derive::{aoc, aoc_generator};
 use color_eyre::eyre::{eyre, Result};
+use itertools::Itertools;
 use once_cell::sync::Lazy;
+use rayon::prelude::*;
+use std::{
+    fmt::{self, Write},
+    ops::ControlFlow,
+};
 
 static COLOR_EYRE: Lazy<()> = Lazy::new(|| color_eyre::install().unwrap());
 
@@ -16,67 +22,385 @@ struct Equation {
     operands: Vec<u64>,
 }
 
-#[aoc(day7, part1)]
-fn part1(equations: &[Equation]) -> u64 {
-    equations
-        .iter()
-        .filter(|eq| hits_target(eq))
-        .map(|eq| eq.target)
-        .sum()
+/// A binary operator that can sit between two operands, always evaluated left to right.
+///
+/// Only the first three are needed by the puzzle; the rest are there for experimenting, so only
+/// the tests build them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Operator {
+    Add,
+    Multiply,
+    /// Appends the digits of the right operand to the left one, so `12 || 345` is `12345`.
+    Concat,
+    #[cfg_attr(not(test), allow(dead_code))]
+    Subtract,
+    /// Exact division only, since a remainder can never lead back to an integer target.
+    #[cfg_attr(not(test), allow(dead_code))]
+    Divide,
+    #[cfg_attr(not(test), allow(dead_code))]
+    Power,
 }
 
-fn hits_target(equation: &Equation) -> bool {
-    equation
-        .operands
-        .iter()
-        .copied()
-        .fold(Vec::new(), |acc, op| {
-            if acc.is_empty() {
-                return vec![op];
-            };
+const PART1_OPERATORS: &[Operator] = &[Operator::Add, Operator::Multiply];
+const PART2_OPERATORS: &[Operator] = &[Operator::Add, Operator::Multiply, Operator::Concat];
 
-            let added = acc.iter().map(|v| v + op);
-            let muled = acc.iter().map(|v| v * op);
+impl Operator {
+    /// Applies the operator, or returns `None` if the result overflows or is not a whole number.
+    fn apply(self, lhs: u64, rhs: u64) -> Option<u64> {
+        match self {
+            Operator::Add => lhs.checked_add(rhs),
+            Operator::Multiply => lhs.checked_mul(rhs),
+            Operator::Concat => lhs
+                .checked_mul(10u64.checked_pow(digits(rhs))?)?
+                .checked_add(rhs),
+            Operator::Subtract => lhs.checked_sub(rhs),
+            Operator::Divide => lhs
+                .checked_rem(rhs)
+                .filter(|rem| *rem == 0)
+                .and_then(|_| lhs.checked_div(rhs)),
+            Operator::Power => match u32::try_from(rhs) {
+                Ok(exponent) => lhs.checked_pow(exponent),
+                // Only 0 and 1 survive such a large exponent without overflowing.
+                Err(_) => (lhs <= 1).then_some(lhs),
+            },
+        }
+    }
 
-            added.chain(muled).collect()
-        })
-        .iter()
-        .any(|v| *v == equation.target)
-}
-
-fn hits_target_2(equation: &Equation) -> bool {
-    equation
-        .operands
-        .iter()
-        .copied()
-        .fold(Vec::new(), |acc, op| {
-            if acc.is_empty() {
-                return vec![op];
-            };
-
-            let added = acc.iter().map(|v| v + op);
-            let muled = acc.iter().map(|v| v * op);
-            let concated = acc.iter().map(|v| {
-                let v_str = v.to_string();
-                let op_str = op.to_string();
-                (v_str + &op_str).parse::<u64>().unwrap()
-            });
-
-            added.chain(muled).chain(concated).collect()
-        })
-        .iter()
-        .any(|v| *v == equation.target)
+    fn symbol(self) -> &'static str {
+        match self {
+            Operator::Add => "+",
+            Operator::Multiply => "*",
+            Operator::Concat => "||",
+            Operator::Subtract => "-",
+            Operator::Divide => "/",
+            Operator::Power => "^",
+        }
+    }
+}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.symbol())
+    }
+}
+
+/// The left operands an [`Operator`] could have been applied to in order to produce a result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Preimage {
+    /// Every left operand works, as when multiplying by zero to reach zero.
+    Any,
+    One(u64),
+    None,
+}
+
+impl From<Option<u64>> for Preimage {
+    fn from(value: Option<u64>) -> Self {
+        value.map_or(Preimage::None, Preimage::One)
+    }
+}
+
+impl Operator {
+    /// Works out which left operands `lhs` satisfy `self.apply(lhs, rhs) == Some(result)`.
+    fn undo(self, result: u64, rhs: u64) -> Preimage {
+        match self {
+            Operator::Add => result.checked_sub(rhs).into(),
+            Operator::Multiply if rhs == 0 => {
+                if result == 0 {
+                    Preimage::Any
+                } else {
+                    Preimage::None
+                }
+            }
+            Operator::Multiply => result.is_multiple_of(rhs).then(|| result / rhs).into(),
+            Operator::Concat => {
+                let Some(shift) = 10u64.checked_pow(digits(rhs)) else {
+                    return Preimage::None;
+                };
+
+                result
+                    .checked_sub(rhs)
+                    .filter(|prefix| prefix % shift == 0)
+                    .map(|prefix| prefix / shift)
+                    .into()
+            }
+            Operator::Subtract => result.checked_add(rhs).into(),
+            Operator::Divide if rhs == 0 => Preimage::None,
+            Operator::Divide => result.checked_mul(rhs).into(),
+            Operator::Power => integer_root(result, rhs),
+        }
+    }
+}
+
+/// The base `b` with `b.pow(exponent) == n`, if there is one.
+fn integer_root(n: u64, exponent: u64) -> Preimage {
+    let Ok(exponent) = u32::try_from(exponent) else {
+        // Only 0 and 1 survive such a large exponent without overflowing.
+        return Preimage::from((n <= 1).then_some(n));
+    };
+
+    match (exponent, n) {
+        (0, 1) => Preimage::Any,
+        (0, _) => Preimage::None,
+        (1, _) => Preimage::One(n),
+        _ => {
+            // The float estimate is only close, so check its neighbours with exact arithmetic.
+            #[allow(
+                clippy::cast_precision_loss,
+                clippy::cast_possible_truncation,
+                clippy::cast_sign_loss
+            )]
+            let estimate = (n as f64).powf(1.0 / f64::from(exponent)).round() as u64;
+
+            (estimate.saturating_sub(1)..=estimate.saturating_add(1))
+                .find(|base| base.checked_pow(exponent) == Some(n))
+                .into()
+        }
+    }
+}
+
+/// The number of decimal digits in `n`, counting `0` as one digit.
+fn digits(n: u64) -> u32 {
+    n.checked_ilog10().map_or(1, |log| log + 1)
+}
+
+#[aoc(day7, part1)]
+fn part1(equations: &[Equation]) -> u64 {
+    total_calibration(equations, PART1_OPERATORS, solvable)
+}
+
+#[aoc(day7, part1, Forward)]
+fn part1_forward(equations: &[Equation]) -> u64 {
+    total_calibration(equations, PART1_OPERATORS, hits_target)
 }
 
 #[aoc(day7, part2)]
 fn part2(equations: &[Equation]) -> u64 {
+    total_calibration(equations, PART2_OPERATORS, solvable)
+}
+
+#[aoc(day7, part2, Forward)]
+fn part2_forward(equations: &[Equation]) -> u64 {
+    total_calibration(equations, PART2_OPERATORS, hits_target)
+}
+
+fn total_calibration(
+    equations: &[Equation],
+    operators: &[Operator],
+    solver: fn(&Equation, &[Operator]) -> bool,
+) -> u64 {
     equations
-        .iter()
-        .filter(|eq| hits_target_2(eq))
+        .par_iter()
+        .filter(|eq| solver(eq, operators))
         .map(|eq| eq.target)
         .sum()
 }
 
+/// Whether any placement of `operators` between the operands evaluates to the target.
+fn solvable(equation: &Equation, operators: &[Operator]) -> bool {
+    witness(equation, operators).is_some()
+}
+
+/// The first placement of `operators` found that evaluates to the target.
+fn witness<'a>(equation: &'a Equation, operators: &[Operator]) -> Option<Expression<'a>> {
+    let mut found = None;
+    let _ = assignments(equation, operators, &mut |chosen| {
+        found = Some(chosen.to_vec());
+        ControlFlow::Break(())
+    });
+
+    found.map(|operators| Expression {
+        operands: &equation.operands,
+        operators,
+    })
+}
+
+/// Every placement of `operators` that evaluates to the target.
+fn solutions<'a>(equation: &'a Equation, operators: &[Operator]) -> Vec<Expression<'a>> {
+    let mut found = vec![];
+    let _ = assignments(equation, operators, &mut |chosen| {
+        found.push(Expression {
+            operands: &equation.operands,
+            operators: chosen.to_vec(),
+        });
+        ControlFlow::Continue(())
+    });
+
+    found
+}
+
+/// Hands each placement of `operators` that evaluates to the target to `found`, until it breaks.
+///
+/// The search works backwards from the target, peeling the last operand off by undoing an
+/// operator, which prunes most branches immediately: a product must divide evenly, a
+/// concatenation must end in the operand's digits and a sum must not go negative.
+fn assignments(
+    equation: &Equation,
+    operators: &[Operator],
+    found: &mut dyn FnMut(&[Operator]) -> ControlFlow<()>,
+) -> ControlFlow<()> {
+    if equation.operands.is_empty() || operators.is_empty() && equation.operands.len() > 1 {
+        return ControlFlow::Continue(());
+    }
+
+    // Every slot is overwritten before `found` sees it, so any operator will do as filler.
+    let mut chosen = vec![Operator::Add; equation.operands.len() - 1];
+
+    undo_from(
+        equation.target,
+        &equation.operands,
+        operators,
+        &mut chosen,
+        found,
+    )
+}
+
+fn undo_from(
+    target: u64,
+    operands: &[u64],
+    operators: &[Operator],
+    chosen: &mut [Operator],
+    found: &mut dyn FnMut(&[Operator]) -> ControlFlow<()>,
+) -> ControlFlow<()> {
+    let Some((last, rest)) = operands.split_last() else {
+        return ControlFlow::Continue(());
+    };
+
+    if rest.is_empty() {
+        return if *last == target {
+            found(chosen)
+        } else {
+            ControlFlow::Continue(())
+        };
+    }
+
+    for operator in operators {
+        chosen[rest.len() - 1] = *operator;
+
+        match operator.undo(target, *last) {
+            Preimage::Any => apply_from(rest[0], &rest[1..], 0, operators, chosen, found)?,
+            Preimage::One(previous) => undo_from(previous, rest, operators, chosen, found)?,
+            Preimage::None => {}
+        }
+    }
+
+    ControlFlow::Continue(())
+}
+
+/// Tries every placement of `operators` between `value` and `operands`, which start at slot
+/// `slot` of `chosen`, reporting those that evaluate without failing. Used once the rest of an
+/// expression accepts any left operand at all, as when it multiplies by zero.
+fn apply_from(
+    value: u64,
+    operands: &[u64],
+    slot: usize,
+    operators: &[Operator],
+    chosen: &mut [Operator],
+    found: &mut dyn FnMut(&[Operator]) -> ControlFlow<()>,
+) -> ControlFlow<()> {
+    let Some((next, rest)) = operands.split_first() else {
+        return found(chosen);
+    };
+
+    for operator in operators {
+        if let Some(value) = operator.apply(value, *next) {
+            chosen[slot] = *operator;
+            apply_from(value, rest, slot + 1, operators, chosen, found)?;
+        }
+    }
+
+    ControlFlow::Continue(())
+}
+
+/// Operands joined by operators, evaluated strictly left to right.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Expression<'a> {
+    operands: &'a [u64],
+    operators: Vec<Operator>,
+}
+
+impl fmt::Display for Expression<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some((first, rest)) = self.operands.split_first() else {
+            return Ok(());
+        };
+
+        write!(f, "{first}")?;
+        for (operator, operand) in self.operators.iter().zip(rest) {
+            write!(f, " {operator} {operand}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Lists every way of solving each equation in `input` with the part one operators, or with the
+/// part two operators when `concat` is set.
+///
+/// # Errors
+///
+/// Fails if the input does not parse.
+pub fn explain(input: &str, concat: bool) -> Result<String> {
+    let equations = parser(input)?;
+    let operators = if concat {
+        PART2_OPERATORS
+    } else {
+        PART1_OPERATORS
+    };
+
+    Ok(explain_equations(&equations, operators))
+}
+
+fn explain_equations(equations: &[Equation], operators: &[Operator]) -> String {
+    let mut explanation = String::new();
+
+    for equation in equations {
+        let solutions = solutions(equation, operators);
+        let operands = equation.operands.iter().join(" ");
+
+        match solutions.len() {
+            0 => writeln!(
+                explanation,
+                "{}: {operands} has no solution",
+                equation.target
+            ),
+            1 => writeln!(
+                explanation,
+                "{}: {operands} has 1 solution",
+                equation.target
+            ),
+            n => writeln!(
+                explanation,
+                "{}: {operands} has {n} solutions",
+                equation.target
+            ),
+        }
+        .unwrap();
+
+        for solution in solutions {
+            writeln!(explanation, "  {} = {solution}", equation.target).unwrap();
+        }
+    }
+
+    explanation
+}
+
+/// Whether any placement of `operators` between the operands evaluates to the target, expanding
+/// every combination from the left. Branches that overflow are dropped rather than wrapped around.
+fn hits_target(equation: &Equation, operators: &[Operator]) -> bool {
+    let Some((first, rest)) = equation.operands.split_first() else {
+        return false;
+    };
+
+    rest.iter()
+        .fold(vec![*first], |acc, operand| {
+            acc.iter()
+                .cartesian_product(operators)
+                .filter_map(|(value, operator)| operator.apply(*value, *operand))
+                .collect()
+        })
+        .contains(&equation.target)
+}
+
 mod parsers {
     use crate::day7::Equation;
     use nom::{
@@ -95,7 +419,7 @@ mod parsers {
 
     type Span<'a> = LocatedSpan<&'a str>;
 
-    pub(super) fn parse_input(input: &str) -> color_eyre::Result<Vec<Equation>, ParseError> {
+    pub(super) fn parse_input(input: &str) -> color_eyre::Result<Vec<Equation>, ParseError<'_>> {
         final_parser(equations)(Span::new(input))
     }
 
@@ -119,7 +443,6 @@ mod tests {
     use indoc::indoc;
     use pretty_assertions::assert_eq;
     use rstest::rstest;
-    use std::fs;
 
     const SAMPLE: &str = indoc! {
         "190: 10 19
@@ -141,7 +464,9 @@ mod tests {
 
     #[rstest]
     #[case::part1(part1, 3749)]
+    #[case::part1_forward(part1_forward, 3749)]
     #[case::part2(part2, 11387)]
+    #[case::part2_forward(part2_forward, 11387)]
     fn sample_tests(#[case] f: fn(&[Equation]) -> u64, #[case] expected: u64) {
         let parsed = parser(SAMPLE).unwrap();
         let result = f(&parsed);
@@ -149,14 +474,175 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_witness() {
+        let parsed = parser(SAMPLE).unwrap();
+
+        let witnesses = parsed
+            .iter()
+            .map(|equation| witness(equation, PART2_OPERATORS).map(|e| e.to_string()))
+            .collect_vec();
+
+        assert_eq!(
+            witnesses,
+            vec![
+                Some("10 * 19".to_string()),
+                Some("81 * 40 + 27".to_string()),
+                None,
+                Some("15 || 6".to_string()),
+                Some("6 * 8 || 6 * 15".to_string()),
+                None,
+                Some("17 || 8 + 14".to_string()),
+                None,
+                Some("11 + 6 * 16 + 20".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_solution_counts() {
+        let parsed = parser(SAMPLE).unwrap();
+
+        let counts = parsed
+            .iter()
+            .map(|equation| solutions(equation, PART1_OPERATORS).len())
+            .collect_vec();
+        assert_eq!(counts, vec![1, 2, 0, 0, 0, 0, 0, 0, 1]);
+
+        let zero = Equation {
+            target: 0,
+            operands: vec![3, 4, 0],
+        };
+        assert_eq!(solutions(&zero, PART1_OPERATORS).len(), 2);
+        assert_eq!(solutions(&zero, &[Operator::Multiply]).len(), 1);
+    }
+
     #[rstest]
-    #[case::part1(part1, 3_245_122_495_150)]
-    #[case::part2(part2, 105_517_128_211_543)]
-    fn prod_tests(#[case] f: fn(&[Equation]) -> u64, #[case] expected: u64) {
-        let input = fs::read_to_string("input/2024/day7.txt").unwrap();
-        let parsed = parser(input.trim_end()).unwrap();
-        let result = f(&parsed);
+    #[case::part1("explanations_part1", PART1_OPERATORS)]
+    #[case::part2("explanations_part2", PART2_OPERATORS)]
+    fn test_explanations(#[case] name: &str, #[case] operators: &[Operator]) {
+        let parsed = parser(SAMPLE).unwrap();
+        insta::assert_snapshot!(name, explain_equations(&parsed, operators));
+    }
 
-        assert_eq!(result, expected);
+    #[rstest]
+    #[case(Operator::Add, 15, 6, Some(21))]
+    #[case(Operator::Multiply, 15, 6, Some(90))]
+    #[case(Operator::Concat, 15, 6, Some(156))]
+    #[case(Operator::Concat, 12, 345, Some(12_345))]
+    #[case(Operator::Concat, 7, 0, Some(70))]
+    #[case(Operator::Concat, 0, 10, Some(10))]
+    #[case(Operator::Subtract, 15, 6, Some(9))]
+    #[case(Operator::Subtract, 6, 15, None)]
+    #[case(Operator::Divide, 15, 5, Some(3))]
+    #[case(Operator::Divide, 15, 6, None)]
+    #[case(Operator::Divide, 15, 0, None)]
+    #[case(Operator::Power, 2, 10, Some(1024))]
+    #[case(Operator::Add, u64::MAX, 1, None)]
+    #[case(Operator::Multiply, u64::MAX, 2, None)]
+    #[case(Operator::Concat, u64::MAX / 10, 99, None)]
+    #[case(Operator::Power, 2, 64, None)]
+    #[case(Operator::Power, 1, u64::MAX, Some(1))]
+    #[case(Operator::Power, 0, u64::MAX, Some(0))]
+    #[case(Operator::Power, 2, u64::MAX, None)]
+    fn operators(
+        #[case] operator: Operator,
+        #[case] lhs: u64,
+        #[case] rhs: u64,
+        #[case] expected: Option<u64>,
+    ) {
+        assert_eq!(operator.apply(lhs, rhs), expected);
+    }
+
+    #[rstest]
+    #[case(0, 1)]
+    #[case(9, 1)]
+    #[case(10, 2)]
+    #[case(999, 3)]
+    #[case(u64::MAX, 20)]
+    fn digit_counts(#[case] n: u64, #[case] expected: u32) {
+        assert_eq!(digits(n), expected);
+    }
+
+    #[test]
+    fn extra_operators() {
+        let equation = Equation {
+            target: 17,
+            operands: vec![2, 5, 15],
+        };
+
+        assert!(!hits_target(&equation, PART2_OPERATORS));
+        assert!(hits_target(
+            &equation,
+            &[Operator::Power, Operator::Subtract]
+        ));
+        assert!(solvable(&equation, &[Operator::Power, Operator::Subtract]));
+
+        let single = Equation {
+            target: 17,
+            operands: vec![17],
+        };
+        assert!(hits_target(&single, &[]));
+        assert!(solvable(&single, &[]));
+        assert!(!solvable(&equation, &[]));
+    }
+
+    #[rstest]
+    #[case(Operator::Add, 21, 6, Preimage::One(15))]
+    #[case(Operator::Add, 5, 6, Preimage::None)]
+    #[case(Operator::Multiply, 90, 6, Preimage::One(15))]
+    #[case(Operator::Multiply, 91, 6, Preimage::None)]
+    #[case(Operator::Multiply, 0, 0, Preimage::Any)]
+    #[case(Operator::Multiply, 5, 0, Preimage::None)]
+    #[case(Operator::Concat, 156, 6, Preimage::One(15))]
+    #[case(Operator::Concat, 12_345, 345, Preimage::One(12))]
+    #[case(Operator::Concat, 12_345, 45, Preimage::One(123))]
+    #[case(Operator::Concat, 12_345, 35, Preimage::None)]
+    #[case(Operator::Concat, 70, 0, Preimage::One(7))]
+    #[case(Operator::Concat, 5, 5, Preimage::One(0))]
+    #[case(Operator::Subtract, 9, 6, Preimage::One(15))]
+    #[case(Operator::Divide, 3, 5, Preimage::One(15))]
+    #[case(Operator::Divide, 3, 0, Preimage::None)]
+    #[case(Operator::Power, 1024, 10, Preimage::One(2))]
+    #[case(Operator::Power, 1000, 10, Preimage::None)]
+    #[case(Operator::Power, 1, 0, Preimage::Any)]
+    #[case(Operator::Power, u64::MAX, 1, Preimage::One(u64::MAX))]
+    #[case(Operator::Power, 3_u64.pow(40), 40, Preimage::One(3))]
+    fn undoing(
+        #[case] operator: Operator,
+        #[case] result: u64,
+        #[case] rhs: u64,
+        #[case] expected: Preimage,
+    ) {
+        assert_eq!(operator.undo(result, rhs), expected);
+
+        if let Preimage::One(lhs) = expected {
+            assert_eq!(operator.apply(lhs, rhs), Some(result));
+        }
+    }
+
+    #[test]
+    fn solvers_agree() {
+        let operators = [
+            Operator::Add,
+            Operator::Multiply,
+            Operator::Concat,
+            Operator::Subtract,
+            Operator::Divide,
+            Operator::Power,
+        ];
+        let parsed = parser(SAMPLE).unwrap();
+
+        for count in 1..=operators.len() {
+            for subset in operators.iter().copied().combinations(count) {
+                for equation in &parsed {
+                    assert_eq!(
+                        solvable(equation, &subset),
+                        hits_target(equation, &subset),
+                        "{equation:?} with {subset:?}"
+                    );
+                }
+            }
+        }
     }
 }
@@ -1,13 +1,18 @@
 #![warn(clippy::pedantic)]
 
 use aoc_runner_derive::aoc_lib;
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
+pub mod answers;
+pub mod benchmarks;
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod scaffold;
+pub mod solutions;
+pub mod submit;
 pub mod utils;
 
 aoc_lib! {year = 2024}
@@ -0,0 +1,463 @@
+//! Starting a new day: a `src/dayN.rs` laid out like the others, its `mod` in `lib.rs`, and its
+//! parts in the `SOLUTIONS` table.
+//!
+//! The module parses lines of numbers and has both parts return an error until they are solved,
+//! with a `SAMPLE` that can be seeded from a saved copy of the puzzle's description.
+
+use color_eyre::eyre::{bail, eyre, Result, WrapErr};
+use itertools::Itertools;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+static PRE_CODE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)<pre><code>(.*?)</code></pre>").unwrap());
+static FENCED: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)```[^\n]*\n(.*?)```").unwrap());
+static TAG: Lazy<Regex> = Lazy::new(|| Regex::new(r"<[^>]*>").unwrap());
+static MODULE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(?:pub )?mod (\w+);$").unwrap());
+static SOLUTION_ENTRY: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s*solution!\((\d+),").unwrap());
+
+const TEMPLATE: &str = r#"use aoc_runner_derive::{aoc, aoc_generator};
+use color_eyre::eyre::{eyre, Result};
+use once_cell::sync::Lazy;
+
+static COLOR_EYRE: Lazy<()> = Lazy::new(|| color_eyre::install().unwrap());
+
+type Line = Vec<u64>;
+
+#[aoc_generator(day{{day}})]
+fn parser(input: &str) -> Result<Vec<Line>> {
+    Lazy::get(&COLOR_EYRE);
+    parsers::parse_input(input).map_err(|e| eyre!(e.to_string()))
+}
+
+#[aoc(day{{day}}, part1)]
+fn part1(_lines: &[Line]) -> Result<u64> {
+    Err(eyre!("day {{day}} part 1 is not solved yet"))
+}
+
+#[aoc(day{{day}}, part2)]
+fn part2(_lines: &[Line]) -> Result<u64> {
+    Err(eyre!("day {{day}} part 2 is not solved yet"))
+}
+
+mod parsers {
+    use crate::day{{day}}::Line;
+    use nom::{
+        character::complete::{self, newline, space1},
+        multi::separated_list1,
+        IResult, Parser,
+    };
+    use nom_locate::LocatedSpan;
+    use nom_supreme::{error::ErrorTree, final_parser::final_parser};
+
+    type ParseError<'a> = ErrorTree<Span<'a>>;
+
+    type Span<'a> = LocatedSpan<&'a str>;
+
+    pub(super) fn parse_input(input: &str) -> color_eyre::Result<Vec<Line>, ParseError<'_>> {
+        final_parser(lines)(Span::new(input))
+    }
+
+    fn lines(input: Span) -> IResult<Span, Vec<Line>, ParseError> {
+        separated_list1(newline, line).parse(input)
+    }
+
+    fn line(input: Span) -> IResult<Span, Line, ParseError> {
+        separated_list1(space1, complete::u64).parse(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::input::InputCache;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    const SAMPLE: &str = indoc! {
+        "{{sample}}"
+    };
+
+    #[test]
+    fn test_parsing() {
+        let parsed = parser(SAMPLE).unwrap();
+        insta::assert_debug_snapshot!(parsed);
+    }
+
+    #[rstest]
+    #[case::part1(part1, 0)]
+    #[case::part2(part2, 0)]
+    fn sample_tests(#[case] f: fn(&[Line]) -> Result<u64>, #[case] expected: u64) {
+        let parsed = parser(SAMPLE).unwrap();
+        let result = f(&parsed).unwrap();
+
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case::part1(part1, 0)]
+    #[case::part2(part2, 0)]
+    fn prod_tests(#[case] f: fn(&[Line]) -> Result<u64>, #[case] expected: u64) {
+        let input = match InputCache::from_env().load({{day}}) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("skipping day {{day}} prod test: {e:#}");
+                return;
+            }
+        };
+        let parsed = parser(&input).unwrap();
+        let result = f(&parsed).unwrap();
+
+        assert_eq!(result, expected);
+    }
+}
+"#;
+
+/// The source of a new day's module, with `sample` as its `SAMPLE`.
+#[must_use]
+pub fn render_day(day: u32, sample: Option<&str>) -> String {
+    // Lines after the first line up under it, for `indoc!` to strip.
+    let sample = sample
+        .unwrap_or_default()
+        .trim_end()
+        .replace('\\', r"\\")
+        .replace('"', "\\\"")
+        .lines()
+        .enumerate()
+        .map(|(number, line)| match (number, line.is_empty()) {
+            (0, _) | (_, true) => line.to_string(),
+            _ => format!("         {line}"),
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    TEMPLATE
+        .replace("{{day}}", &day.to_string())
+        .replace("{{sample}}", &sample)
+}
+
+/// The puzzle's example from a saved description: the first `<pre><code>` block of the HTML page,
+/// the first fenced block of a Markdown copy, or failing both, the whole file.
+#[must_use]
+pub fn extract_sample(description: &str) -> Option<String> {
+    let sample = if let Some(code) = PRE_CODE.captures(description) {
+        let text = TAG.replace_all(&code[1], "");
+        text.replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&#39;", "'")
+            .replace("&amp;", "&")
+    } else if let Some(code) = FENCED.captures(description) {
+        code[1].to_string()
+    } else {
+        description.to_string()
+    };
+
+    let sample = sample.trim_end().to_string();
+    (!sample.trim().is_empty()).then_some(sample)
+}
+
+/// `lib.rs` with `mod dayN;` among the other modules, sorted by name as rustfmt sorts them, so
+/// `day10` comes before `day2`.
+///
+/// # Errors
+///
+/// Fails if the day is already there, or there are no modules to put it beside.
+pub fn register_module(lib_rs: &str, day: u32) -> Result<String> {
+    let name = format!("day{day}");
+    let mut lines: Vec<&str> = lib_rs.lines().collect();
+    let modules: Vec<(usize, &str)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| Some((index, MODULE.captures(line)?.get(1)?.as_str())))
+        .collect();
+
+    if modules.iter().any(|(_, existing)| *existing == name) {
+        bail!("lib.rs already has a module for day {day}");
+    }
+
+    let index = modules
+        .iter()
+        .find(|(_, existing)| *existing > name.as_str())
+        .map(|(index, _)| *index)
+        .or_else(|| modules.last().map(|(index, _)| index + 1))
+        .ok_or_else(|| eyre!("lib.rs has no modules to add day {day} beside"))?;
+
+    let module = format!("mod {name};");
+    lines.insert(index, &module);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// `solutions.rs` with the new day's parts imported and listed in `SOLUTIONS`, in day order, laid
+/// out as rustfmt would.
+///
+/// # Errors
+///
+/// Fails if the day is already listed, or the file is not laid out as expected.
+pub fn register_solutions(solutions_rs: &str, day: u32) -> Result<String> {
+    let traits = [format!("Day{day}Part1"), format!("Day{day}Part2")];
+    let entries = [
+        format!("    solution!({day}, 1, Factory::day{day}_part1),"),
+        format!("    solution!({day}, 2, Factory::day{day}_part2),"),
+    ];
+
+    let import_start = solutions_rs
+        .find("use crate::{")
+        .ok_or_else(|| eyre!("solutions.rs does not import from crate"))?;
+    let import_end = import_start
+        + solutions_rs[import_start..]
+            .find("};")
+            .ok_or_else(|| eyre!("solutions.rs has an unterminated import"))?;
+
+    let mut names: Vec<String> = solutions_rs[import_start + "use crate::{".len()..import_end]
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect();
+    if names.iter().any(|name| traits.contains(name)) {
+        bail!("solutions.rs already lists day {day}");
+    }
+    names.extend(traits);
+    names.sort();
+
+    let import = crate_import(&names);
+    let rest = &solutions_rs[import_end + "};".len()..];
+
+    let table_start = rest
+        .find("pub const SOLUTIONS")
+        .ok_or_else(|| eyre!("solutions.rs has no SOLUTIONS table"))?;
+    let table_end = table_start
+        + rest[table_start..]
+            .find("\n];")
+            .ok_or_else(|| eyre!("solutions.rs has an unterminated SOLUTIONS table"))?;
+
+    let mut table: Vec<String> = rest[table_start..table_end]
+        .lines()
+        .map(str::to_string)
+        .collect();
+    let index = table
+        .iter()
+        .position(|line| {
+            SOLUTION_ENTRY
+                .captures(line)
+                .and_then(|captures| captures[1].parse::<u32>().ok())
+                .is_some_and(|existing| existing > day)
+        })
+        .unwrap_or(table.len());
+    table.splice(index..index, entries);
+
+    Ok(format!(
+        "{}{import}{}{}{}",
+        &solutions_rs[..import_start],
+        &rest[..table_start],
+        table.join("\n"),
+        &rest[table_end..]
+    ))
+}
+
+/// `use crate::{...};` for `names`, on one line if it fits and otherwise wrapped the way rustfmt
+/// wraps a long import list.
+fn crate_import(names: &[String]) -> String {
+    const MAX_WIDTH: usize = 100;
+    const INDENT: &str = "    ";
+
+    let single = format!("use crate::{{{}}};", names.join(", "));
+    if single.len() <= MAX_WIDTH {
+        return single;
+    }
+
+    // Fill each line with as many names as fit, counting the space before a name and its comma.
+    // rustfmt keeps a wrapped line short of the limit itself.
+    let mut lines: Vec<String> = Vec::new();
+    for name in names {
+        match lines.last_mut() {
+            Some(line) if INDENT.len() + line.len() + name.len() + 2 < MAX_WIDTH => {
+                line.push(' ');
+                line.push_str(name);
+                line.push(',');
+            }
+            _ => lines.push(format!("{name},")),
+        }
+    }
+
+    let body = lines
+        .iter()
+        .map(|line| format!("{INDENT}{line}"))
+        .join("\n");
+    format!("use crate::{{\n{body}\n}};")
+}
+
+/// Writes the new day into the crate at `root`, returning the files it created or changed.
+///
+/// # Errors
+///
+/// Fails if the day already exists, or any of the files cannot be read or written. Nothing is
+/// written unless every change can be made.
+pub fn scaffold(root: &Path, day: u32, sample: Option<&str>) -> Result<Vec<PathBuf>> {
+    if !(1..=25).contains(&day) {
+        bail!("there is no day {day}, puzzles run from day 1 to day 25");
+    }
+
+    let module_path = root.join("src").join(format!("day{day}.rs"));
+    if module_path.exists() {
+        bail!("{} already exists", module_path.display());
+    }
+
+    let lib_path = root.join("src/lib.rs");
+    let solutions_path = root.join("src/solutions.rs");
+    let lib_rs = register_module(&read(&lib_path)?, day)?;
+    let solutions_rs = register_solutions(&read(&solutions_path)?, day)?;
+
+    for (path, contents) in [
+        (&module_path, render_day(day, sample)),
+        (&lib_path, lib_rs),
+        (&solutions_path, solutions_rs),
+    ] {
+        fs::write(path, contents)
+            .wrap_err_with(|| format!("could not write {}", path.display()))?;
+    }
+
+    Ok(vec![module_path, lib_path, solutions_path])
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path).wrap_err_with(|| format!("could not read {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    const DESCRIPTION: &str = indoc! {r#"
+        <article class="day-desc"><h2>--- Day 8: Example ---</h2>
+        <p>For example:</p>
+        <pre><code>1 2 3
+        &lt;4&gt; <em>5</em> 6
+
+        "7" &amp; 8
+        </code></pre>
+        <p>Another block:</p>
+        <pre><code>9 9 9
+        </code></pre>
+        </article>
+    "#};
+
+    #[test]
+    fn samples_from_descriptions() {
+        assert_eq!(
+            extract_sample(DESCRIPTION).unwrap(),
+            "1 2 3\n<4> 5 6\n\n\"7\" & 8"
+        );
+        assert_eq!(
+            extract_sample("Example:\n\n```text\n1 2\n3 4\n```\n").unwrap(),
+            "1 2\n3 4"
+        );
+        assert_eq!(extract_sample("5 6\n7 8\n\n").unwrap(), "5 6\n7 8");
+        assert_eq!(extract_sample("\n \n"), None);
+    }
+
+    #[test]
+    fn rendering() {
+        let sample = extract_sample(DESCRIPTION).unwrap();
+        insta::assert_snapshot!(render_day(8, Some(&sample)));
+    }
+
+    #[test]
+    fn module_registration() {
+        let lib_rs = "use aoc_runner_derive::aoc_lib;\npub mod answers;\npub mod day1;\nmod day2;\npub mod day7;\npub mod solutions;\n";
+
+        assert_eq!(
+            register_module(lib_rs, 8).unwrap(),
+            "use aoc_runner_derive::aoc_lib;\npub mod answers;\npub mod day1;\nmod day2;\npub mod day7;\nmod day8;\npub mod solutions;\n"
+        );
+        assert_eq!(
+            register_module(lib_rs, 5).unwrap(),
+            "use aoc_runner_derive::aoc_lib;\npub mod answers;\npub mod day1;\nmod day2;\nmod day5;\npub mod day7;\npub mod solutions;\n"
+        );
+        assert_eq!(
+            register_module(lib_rs, 12).unwrap(),
+            "use aoc_runner_derive::aoc_lib;\npub mod answers;\npub mod day1;\nmod day12;\nmod day2;\npub mod day7;\npub mod solutions;\n"
+        );
+        assert_eq!(
+            register_module(lib_rs, 2).unwrap_err().to_string(),
+            "lib.rs already has a module for day 2"
+        );
+    }
+
+    const LIB_RS: &str = indoc! {"
+        use aoc_runner_derive::aoc_lib;
+        pub mod day1;
+        mod day2;
+        pub mod day4;
+        pub mod solutions;
+
+        aoc_lib! {year = 2024}
+    "};
+
+    const SOLUTIONS_RS: &str = indoc! {r#"
+        use crate::{
+            Day1Part1, Day1Part2, Day2Part1, Day2Part2, Day4Part1, Day4Part1FAST, Day4Part2, Factory,
+        };
+
+        pub const SOLUTIONS: &[Solution] = &[
+            solution!(1, 1, Factory::day1_part1),
+            solution!(1, 2, Factory::day1_part2),
+            solution!(2, 1, Factory::day2_part1),
+            solution!(2, 2, Factory::day2_part2),
+            solution!(4, 1, Factory::day4_part1),
+            solution!(4, 1, "Fast", Factory::day4_part1_fast),
+            solution!(4, 2, Factory::day4_part2),
+        ];
+    "#};
+
+    #[test]
+    fn solutions_registration() {
+        insta::assert_snapshot!(register_solutions(SOLUTIONS_RS, 3).unwrap());
+
+        let last = register_solutions(SOLUTIONS_RS, 5).unwrap();
+        assert!(last.contains(
+            "    solution!(4, 2, Factory::day4_part2),\n    \
+             solution!(5, 1, Factory::day5_part1),\n    \
+             solution!(5, 2, Factory::day5_part2),\n];"
+        ));
+
+        assert_eq!(
+            register_solutions(SOLUTIONS_RS, 4).unwrap_err().to_string(),
+            "solutions.rs already lists day 4"
+        );
+    }
+
+    #[test]
+    fn scaffolding() {
+        let root = tempfile::tempdir().unwrap();
+        fs::create_dir(root.path().join("src")).unwrap();
+        fs::write(root.path().join("src/lib.rs"), LIB_RS).unwrap();
+        fs::write(root.path().join("src/solutions.rs"), SOLUTIONS_RS).unwrap();
+
+        let written = scaffold(root.path(), 3, None).unwrap();
+        assert_eq!(written.len(), 3);
+        assert!(fs::read_to_string(root.path().join("src/day3.rs"))
+            .unwrap()
+            .contains("#[aoc(day3, part2)]"));
+        assert!(fs::read_to_string(root.path().join("src/lib.rs"))
+            .unwrap()
+            .contains("mod day2;\nmod day3;\npub mod day4;\n"));
+
+        assert_eq!(
+            scaffold(root.path(), 3, None).unwrap_err().to_string(),
+            format!(
+                "{} already exists",
+                root.path().join("src/day3.rs").display()
+            )
+        );
+        assert!(scaffold(root.path(), 26, None).is_err());
+    }
+}
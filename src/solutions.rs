@@ -0,0 +1,145 @@
+//! A table of every `#[aoc]` solution, so binaries can pick one by day, part and name at runtime
+//! instead of calling a particular `Factory` method.
+
+use crate::{
+    Day1Part1, Day1Part1STREAMING, Day1Part2, Day1Part2STREAMING, Day2Part1, Day2Part2, Day3Part1,
+    Day3Part2, Day4Part1, Day4Part2, Day5Part1, Day5Part2, Day6Part1, Day6Part2,
+    Day6Part2SIMULATED, Day7Part1, Day7Part1FORWARD, Day7Part2, Day7Part2FORWARD, Factory,
+};
+use aoc_runner::{ArcStr, Runner};
+use std::{error::Error, fmt};
+
+/// Runs a day's generator over the input, returning something that can run the solution.
+pub type Constructor = fn(ArcStr) -> Result<Box<dyn Runner>, Box<dyn Error>>;
+
+/// One `#[aoc(dayN, partM)]` or `#[aoc(dayN, partM, Name)]` function.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub day: u32,
+    pub part: u32,
+    /// The name of an alternative solution, or `None` for the main one.
+    pub name: Option<&'static str>,
+    pub construct: Constructor,
+}
+
+impl fmt::Debug for Solution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Solution")
+            .field("day", &self.day)
+            .field("part", &self.part)
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Matches the labels `cargo aoc` prints, like `Day 6 - Part 2 - Simulated`.
+impl fmt::Display for Solution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Day {} - Part {}", self.day, self.part)?;
+        if let Some(name) = self.name {
+            write!(f, " - {name}")?;
+        }
+
+        Ok(())
+    }
+}
+
+macro_rules! solution {
+    ($day:literal, $part:literal, $constructor:expr) => {
+        Solution {
+            day: $day,
+            part: $part,
+            name: None,
+            construct: $constructor,
+        }
+    };
+    ($day:literal, $part:literal, $name:literal, $constructor:expr) => {
+        Solution {
+            day: $day,
+            part: $part,
+            name: Some($name),
+            construct: $constructor,
+        }
+    };
+}
+
+/// Every solution, ordered by day, then part, with the main solution before named ones.
+pub const SOLUTIONS: &[Solution] = &[
+    solution!(1, 1, Factory::day1_part1),
+    solution!(1, 1, "Streaming", Factory::day1_part1_streaming),
+    solution!(1, 2, Factory::day1_part2),
+    solution!(1, 2, "Streaming", Factory::day1_part2_streaming),
+    solution!(2, 1, Factory::day2_part1),
+    solution!(2, 2, Factory::day2_part2),
+    solution!(3, 1, Factory::day3_part1),
+    solution!(3, 2, Factory::day3_part2),
+    solution!(4, 1, Factory::day4_part1),
+    solution!(4, 2, Factory::day4_part2),
+    solution!(5, 1, Factory::day5_part1),
+    solution!(5, 2, Factory::day5_part2),
+    solution!(6, 1, Factory::day6_part1),
+    solution!(6, 2, Factory::day6_part2),
+    solution!(6, 2, "Simulated", Factory::day6_part2_simulated),
+    solution!(7, 1, Factory::day7_part1),
+    solution!(7, 1, "Forward", Factory::day7_part1_forward),
+    solution!(7, 2, Factory::day7_part2),
+    solution!(7, 2, "Forward", Factory::day7_part2_forward),
+];
+
+/// Looks up a solution, ignoring the case of `name`.
+#[must_use]
+pub fn find(day: u32, part: u32, name: Option<&str>) -> Option<&'static Solution> {
+    SOLUTIONS.iter().find(|solution| {
+        solution.day == day
+            && solution.part == part
+            && match (solution.name, name) {
+                (None, None) => true,
+                (Some(expected), Some(name)) => expected.eq_ignore_ascii_case(name),
+                _ => false,
+            }
+    })
+}
+
+/// The main solution of every part of `day`.
+pub fn for_day(day: u32) -> impl Iterator<Item = &'static Solution> {
+    SOLUTIONS
+        .iter()
+        .filter(move |solution| solution.day == day && solution.name.is_none())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn every_day_has_both_parts() {
+        let days = SOLUTIONS.iter().map(|s| s.day).unique().collect_vec();
+        let last = *days.last().unwrap();
+        assert_eq!(days, (1..=last).collect_vec());
+
+        for day in days {
+            let parts = for_day(day).map(|s| s.part).collect_vec();
+            assert_eq!(parts, vec![1, 2], "day {day}");
+        }
+    }
+
+    #[test]
+    fn lookup() {
+        let solution = find(6, 2, Some("simulated")).unwrap();
+        assert_eq!(solution.to_string(), "Day 6 - Part 2 - Simulated");
+
+        assert_eq!(find(6, 2, None).unwrap().to_string(), "Day 6 - Part 2");
+        assert!(find(6, 1, Some("Simulated")).is_none());
+        assert!(find(26, 1, None).is_none());
+    }
+
+    #[test]
+    fn dispatch() {
+        let runner = (find(1, 2, None).unwrap().construct)(ArcStr::from("3   4\n4   3\n")).unwrap();
+        assert_eq!(runner.try_run().unwrap().to_string(), "7");
+
+        assert!((find(1, 1, None).unwrap().construct)(ArcStr::from("3   x")).is_err());
+    }
+}
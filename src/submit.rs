@@ -0,0 +1,655 @@
+//! Submitting answers to the Advent of Code site, and remembering what was submitted.
+//!
+//! Every judged guess goes into a history file next to the cached inputs, which is checked before
+//! anything is sent: a part that is already solved, an answer that was already tried, one that is
+//! at least a known "too high" or at most a known "too low", or one sent while the site still wants
+//! us to wait, is refused locally instead of costing another lockout.
+
+use crate::{
+    answers::{input_hash, AnswerKey, Answers},
+    utils::{
+        http::{base_url_from_env, session_from_env, HttpClient, UreqClient},
+        input::YEAR,
+    },
+};
+use color_eyre::eyre::{bail, eyre, Result, WrapErr};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+static ARTICLE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?s)<article[^>]*>(.*?)</article>").unwrap());
+static TAG: Lazy<Regex> = Lazy::new(|| Regex::new(r"<[^>]*>").unwrap());
+static LEFT_TO_WAIT: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap());
+static LOCKOUT: Lazy<Regex> = Lazy::new(|| Regex::new(r"wait (one|\d+) minutes?").unwrap());
+
+/// Where the history of submissions is kept, beside the inputs they were for.
+#[must_use]
+pub fn history_path(input_dir: &Path, year: u32) -> PathBuf {
+    input_dir.join(year.to_string()).join("submissions.toml")
+}
+
+/// What the site made of an answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without saying which way.
+    Wrong,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+            Verdict::Wrong => write!(f, "wrong"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Response {
+    /// The answer was judged, after which the site may refuse answers for a number of seconds.
+    Judged { verdict: Verdict, wait: Option<u64> },
+    /// An earlier answer was too recent for this one to be judged.
+    TooSoon { wait: u64 },
+    /// The part was already solved, so there was nothing to judge.
+    AlreadySolved,
+}
+
+impl fmt::Display for Response {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Response::Judged {
+                verdict,
+                wait: None,
+            } => write!(f, "{verdict}"),
+            Response::Judged {
+                verdict,
+                wait: Some(wait),
+            } => write!(f, "{verdict}, wait {wait}s before answering again"),
+            Response::TooSoon { wait } => {
+                write!(f, "not judged, answered too recently, wait {wait}s")
+            }
+            Response::AlreadySolved => write!(f, "not judged, the part is already solved"),
+        }
+    }
+}
+
+/// Reads the site's reply to an answer, which is an HTML page with the interesting part in its
+/// `<article>`.
+///
+/// # Errors
+///
+/// Fails if the reply is not one of the ones the site is known to give.
+pub fn parse_response(html: &str) -> Result<Response> {
+    let article = ARTICLE
+        .captures(html)
+        .and_then(|captures| captures.get(1))
+        .map_or(html, |article| article.as_str());
+    let text = TAG.replace_all(article, "");
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if text.contains("That's the right answer") {
+        return Ok(Response::Judged {
+            verdict: Verdict::Correct,
+            wait: None,
+        });
+    }
+
+    if text.contains("That's not the right answer") {
+        let verdict = if text.contains("too high") {
+            Verdict::TooHigh
+        } else if text.contains("too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        };
+        let wait = LOCKOUT.captures(&text).map(|captures| match &captures[1] {
+            "one" => 60,
+            minutes => minutes.parse::<u64>().unwrap_or(1) * 60,
+        });
+
+        return Ok(Response::Judged { verdict, wait });
+    }
+
+    if text.contains("You gave an answer too recently") {
+        let wait = LEFT_TO_WAIT
+            .captures(&text)
+            .map(|captures| {
+                let minutes = captures
+                    .get(1)
+                    .map_or(0, |m| m.as_str().parse().unwrap_or(0));
+                let seconds: u64 = captures[2].parse().unwrap_or(0);
+                minutes * 60 + seconds
+            })
+            .ok_or_else(|| eyre!("the site asked to wait without saying how long: {text}"))?;
+
+        return Ok(Response::TooSoon { wait });
+    }
+
+    if text.contains("You don't seem to be solving the right level") {
+        return Ok(Response::AlreadySolved);
+    }
+
+    bail!("unrecognised response from the site: {text}")
+}
+
+/// One judged answer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Guess {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the Unix epoch.
+    pub submitted_at: u64,
+}
+
+/// Why an answer was not sent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Blocked {
+    AlreadySolved { answer: String },
+    AlreadyTried { verdict: Verdict },
+    NotBelowTooHigh { bound: String },
+    NotAboveTooLow { bound: String },
+    Wait { seconds: u64 },
+}
+
+impl fmt::Display for Blocked {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Blocked::AlreadySolved { answer } => {
+                write!(f, "the part was already solved with {answer}")
+            }
+            Blocked::AlreadyTried { verdict } => {
+                write!(f, "it was already tried and was {verdict}")
+            }
+            Blocked::NotBelowTooHigh { bound } => write!(f, "{bound} was already too high"),
+            Blocked::NotAboveTooLow { bound } => write!(f, "{bound} was already too low"),
+            Blocked::Wait { seconds } => write!(
+                f,
+                "the site wants another {seconds}s before the next answer"
+            ),
+        }
+    }
+}
+
+/// Every guess submitted so far, and when the site will take the next one.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct History {
+    /// Seconds since the Unix epoch before which the site will not judge answers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub not_before: Option<u64>,
+    #[serde(default, rename = "guess")]
+    pub guesses: Vec<Guess>,
+}
+
+impl History {
+    /// Reads the history at `path`, which may not exist yet.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the file exists but cannot be read, or is not a valid history.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let text = fs::read_to_string(path)
+            .wrap_err_with(|| format!("could not read {}", path.display()))?;
+        toml::from_str(&text)
+            .wrap_err_with(|| format!("{} is not a valid submission history", path.display()))
+    }
+
+    /// # Errors
+    ///
+    /// Fails if the file cannot be written.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .wrap_err_with(|| format!("could not create {}", parent.display()))?;
+        }
+
+        fs::write(path, toml::to_string(self)?)
+            .wrap_err_with(|| format!("could not write {}", path.display()))
+    }
+
+    fn guesses(&self, year: u32, day: u32, part: u32) -> impl Iterator<Item = &Guess> {
+        self.guesses
+            .iter()
+            .filter(move |guess| (guess.year, guess.day, guess.part) == (year, day, part))
+    }
+
+    /// Whether `answer` is worth sending at `now`, judging by what the site has already said.
+    #[must_use]
+    pub fn check(&self, year: u32, day: u32, part: u32, answer: &str, now: u64) -> Option<Blocked> {
+        if let Some(correct) = self
+            .guesses(year, day, part)
+            .find(|guess| guess.verdict == Verdict::Correct)
+        {
+            return Some(Blocked::AlreadySolved {
+                answer: correct.answer.clone(),
+            });
+        }
+
+        if let Some(tried) = self
+            .guesses(year, day, part)
+            .find(|guess| guess.answer == answer)
+        {
+            return Some(Blocked::AlreadyTried {
+                verdict: tried.verdict,
+            });
+        }
+
+        if let Some(bounded) = self.bounded(year, day, part, answer) {
+            return Some(bounded);
+        }
+
+        self.not_before
+            .filter(|not_before| *not_before > now)
+            .map(|not_before| Blocked::Wait {
+                seconds: not_before - now,
+            })
+    }
+
+    /// Whether a numeric `answer` is on the wrong side of an answer already known to be too high
+    /// or too low.
+    fn bounded(&self, year: u32, day: u32, part: u32, answer: &str) -> Option<Blocked> {
+        let value: i128 = answer.parse().ok()?;
+        let bound = |verdict: Verdict| {
+            self.guesses(year, day, part)
+                .filter(move |guess| guess.verdict == verdict)
+                .filter_map(|guess| Some((guess.answer.parse::<i128>().ok()?, &guess.answer)))
+        };
+
+        if let Some((_, bound)) = bound(Verdict::TooHigh)
+            .filter(|(high, _)| value >= *high)
+            .min()
+        {
+            return Some(Blocked::NotBelowTooHigh {
+                bound: bound.clone(),
+            });
+        }
+
+        if let Some((_, bound)) = bound(Verdict::TooLow)
+            .filter(|(low, _)| value <= *low)
+            .max()
+        {
+            return Some(Blocked::NotAboveTooLow {
+                bound: bound.clone(),
+            });
+        }
+
+        None
+    }
+
+    /// Takes note of what the site said about `answer` at `now`.
+    pub fn record(
+        &mut self,
+        year: u32,
+        day: u32,
+        part: u32,
+        answer: &str,
+        response: Response,
+        now: u64,
+    ) {
+        match response {
+            Response::Judged { verdict, wait } => {
+                self.guesses.push(Guess {
+                    year,
+                    day,
+                    part,
+                    answer: answer.to_string(),
+                    verdict,
+                    submitted_at: now,
+                });
+                self.not_before = wait.map(|wait| now + wait);
+            }
+            Response::TooSoon { wait } => self.not_before = Some(now + wait),
+            Response::AlreadySolved => {}
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Submission {
+    /// The history showed there was no point sending the answer.
+    Blocked(Blocked),
+    Sent(Response),
+}
+
+/// Sends answers to the site for one year's puzzles.
+#[derive(Debug, Clone)]
+pub struct Client<C = UreqClient> {
+    http: C,
+    base_url: String,
+    session: String,
+    year: u32,
+}
+
+impl Client {
+    /// A client for the site at `AOC_BASE_URL`, using the session in `AOC_SESSION`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `AOC_SESSION` is not set.
+    pub fn from_env() -> Result<Self> {
+        let session =
+            session_from_env().ok_or_else(|| eyre!("AOC_SESSION must be set to submit answers"))?;
+        Ok(Client::new(
+            UreqClient::default(),
+            base_url_from_env(),
+            session,
+        ))
+    }
+}
+
+impl<C: HttpClient> Client<C> {
+    pub fn new(http: C, base_url: impl Into<String>, session: impl Into<String>) -> Self {
+        Self {
+            http,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            session: session.into(),
+            year: YEAR,
+        }
+    }
+
+    #[must_use]
+    pub fn year(&self) -> u32 {
+        self.year
+    }
+
+    /// Sends `answer` for the part, unless `history` shows there is no point, and records what the
+    /// site made of it.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the request fails or the site's reply is not understood, in which case `history`
+    /// is left as it was.
+    pub fn submit(
+        &self,
+        history: &mut History,
+        day: u32,
+        part: u32,
+        answer: &str,
+        now: u64,
+    ) -> Result<Submission> {
+        if let Some(blocked) = history.check(self.year, day, part, answer, now) {
+            return Ok(Submission::Blocked(blocked));
+        }
+
+        let url = format!("{}/{}/day/{day}/answer", self.base_url, self.year);
+        let level = part.to_string();
+        let html = self
+            .http
+            .post(
+                &url,
+                &self.session,
+                &[("level", &level), ("answer", answer)],
+            )
+            .wrap_err_with(|| format!("could not submit the answer for day {day} part {part}"))?;
+        let response = parse_response(&html)?;
+
+        history.record(self.year, day, part, answer, response, now);
+        Ok(Submission::Sent(response))
+    }
+}
+
+/// Records an answer the site accepted against the input it was computed from.
+pub fn record_answer(
+    answers: &mut Answers,
+    day: u32,
+    part: u32,
+    input: &str,
+    answer: &str,
+) -> AnswerKey {
+    let key = AnswerKey {
+        year: YEAR,
+        day,
+        part,
+        input: input_hash(input),
+    };
+    answers.insert(key.clone(), answer.to_string());
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        thread,
+    };
+    use tiny_http::{Response as HttpResponse, Server};
+
+    fn page(article: &str) -> String {
+        format!("<html><body><main>\n<article><p>{article}</p></article>\n</main></body></html>")
+    }
+
+    #[rstest]
+    #[case::correct(
+        "That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer.",
+        Response::Judged { verdict: Verdict::Correct, wait: None },
+    )]
+    #[case::too_high(
+        "That's not the right answer; your answer is too high.  Please wait one minute before \
+         trying again. <a href=\"/2024/day/1\">[Return to Day 1]</a>",
+        Response::Judged { verdict: Verdict::TooHigh, wait: Some(60) },
+    )]
+    #[case::too_low(
+        "That's not the right answer; your answer is too low.  Please wait 5 minutes before trying \
+         again.",
+        Response::Judged { verdict: Verdict::TooLow, wait: Some(300) },
+    )]
+    #[case::wrong(
+        "That's not the right answer.  If you're stuck, make sure you're using the full input data.",
+        Response::Judged { verdict: Verdict::Wrong, wait: None },
+    )]
+    #[case::too_soon(
+        "You gave an answer too recently; you have to wait after submitting an answer before \
+         trying again.  You have 32s left to wait.",
+        Response::TooSoon { wait: 32 },
+    )]
+    #[case::too_soon_minutes(
+        "You gave an answer too recently; you have to wait after submitting an answer before \
+         trying again.  You have 4m 5s left to wait.",
+        Response::TooSoon { wait: 245 },
+    )]
+    #[case::already_solved(
+        "You don't seem to be solving the right level.  Did you already complete it?",
+        Response::AlreadySolved
+    )]
+    fn responses(#[case] article: &str, #[case] expected: Response) {
+        assert_eq!(parse_response(&page(article)).unwrap(), expected);
+    }
+
+    #[test]
+    fn unknown_response() {
+        let error = parse_response(&page("<em>Something</em> else")).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "unrecognised response from the site: Something else"
+        );
+    }
+
+    fn judged(verdict: Verdict) -> Response {
+        Response::Judged {
+            verdict,
+            wait: None,
+        }
+    }
+
+    #[test]
+    fn blocking() {
+        let mut history = History::default();
+        history.record(YEAR, 1, 1, "500", judged(Verdict::TooHigh), 0);
+        history.record(YEAR, 1, 1, "800", judged(Verdict::TooHigh), 0);
+        history.record(YEAR, 1, 1, "100", judged(Verdict::TooLow), 0);
+        history.record(YEAR, 1, 1, "abc", judged(Verdict::Wrong), 0);
+
+        assert_eq!(history.check(YEAR, 1, 1, "300", 0), None);
+        assert_eq!(
+            history.check(YEAR, 1, 1, "500", 0),
+            Some(Blocked::AlreadyTried {
+                verdict: Verdict::TooHigh
+            })
+        );
+        assert_eq!(
+            history.check(YEAR, 1, 1, "600", 0),
+            Some(Blocked::NotBelowTooHigh {
+                bound: "500".to_string()
+            })
+        );
+        assert_eq!(
+            history.check(YEAR, 1, 1, "-3", 0),
+            Some(Blocked::NotAboveTooLow {
+                bound: "100".to_string()
+            })
+        );
+        assert_eq!(
+            history.check(YEAR, 1, 1, "abc", 0),
+            Some(Blocked::AlreadyTried {
+                verdict: Verdict::Wrong
+            })
+        );
+        assert_eq!(history.check(YEAR, 1, 1, "xyz", 0), None);
+
+        // Other parts are unaffected, until the site asks for a pause.
+        assert_eq!(history.check(YEAR, 1, 2, "600", 0), None);
+        history.record(YEAR, 1, 2, "600", Response::TooSoon { wait: 30 }, 100);
+        assert_eq!(
+            history.check(YEAR, 2, 1, "1", 110),
+            Some(Blocked::Wait { seconds: 20 })
+        );
+        assert_eq!(history.check(YEAR, 2, 1, "1", 130), None);
+
+        history.record(YEAR, 1, 1, "300", judged(Verdict::Correct), 200);
+        assert_eq!(
+            history.check(YEAR, 1, 1, "301", 0),
+            Some(Blocked::AlreadySolved {
+                answer: "300".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn history_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = history_path(dir.path(), YEAR);
+
+        let mut history = History::load(&path).unwrap();
+        assert_eq!(history, History::default());
+
+        history.record(
+            YEAR,
+            3,
+            2,
+            "48",
+            Response::Judged {
+                verdict: Verdict::TooLow,
+                wait: Some(60),
+            },
+            1_733_000_000,
+        );
+        history.save(&path).unwrap();
+
+        insta::assert_snapshot!(fs::read_to_string(&path).unwrap());
+        assert_eq!(History::load(&path).unwrap(), history);
+    }
+
+    /// A local stand in for the site, whose answer to every part is 42, counting the answers it
+    /// is sent.
+    fn stub_server() -> (String, Arc<AtomicUsize>) {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", server.server_addr().to_ip().unwrap());
+        let requests = Arc::new(AtomicUsize::new(0));
+
+        let counter = Arc::clone(&requests);
+        thread::spawn(move || {
+            for mut request in server.incoming_requests() {
+                counter.fetch_add(1, Ordering::SeqCst);
+
+                let mut form = String::new();
+                request.as_reader().read_to_string(&mut form).unwrap();
+                let answer = form
+                    .split('&')
+                    .find_map(|field| field.strip_prefix("answer="))
+                    .and_then(|answer| answer.parse::<u64>().ok());
+
+                let article = match answer {
+                    Some(42) => "That's the right answer!".to_string(),
+                    Some(answer) => format!(
+                        "That's not the right answer; your answer is too {}.",
+                        if answer > 42 { "high" } else { "low" }
+                    ),
+                    None => "That's not the right answer.".to_string(),
+                };
+                let _ = request.respond(HttpResponse::from_string(page(&article)));
+            }
+        });
+
+        (base_url, requests)
+    }
+
+    #[test]
+    fn submitting() {
+        let (base_url, requests) = stub_server();
+        let client = Client::new(UreqClient::default(), base_url, "abc");
+        let mut history = History::default();
+        let mut submit = |answer| client.submit(&mut history, 1, 2, answer, 0).unwrap();
+
+        assert_eq!(submit("50"), Submission::Sent(judged(Verdict::TooHigh)));
+        assert_eq!(
+            submit("60"),
+            Submission::Blocked(Blocked::NotBelowTooHigh {
+                bound: "50".to_string()
+            })
+        );
+        assert_eq!(submit("10"), Submission::Sent(judged(Verdict::TooLow)));
+        assert_eq!(
+            submit("10"),
+            Submission::Blocked(Blocked::AlreadyTried {
+                verdict: Verdict::TooLow
+            })
+        );
+        assert_eq!(submit("42"), Submission::Sent(judged(Verdict::Correct)));
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+
+        let verdicts = history
+            .guesses
+            .iter()
+            .map(|guess| (guess.answer.as_str(), guess.verdict))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            verdicts,
+            vec![
+                ("50", Verdict::TooHigh),
+                ("10", Verdict::TooLow),
+                ("42", Verdict::Correct)
+            ]
+        );
+
+        let mut answers = Answers::default();
+        answers.insert_unpinned(YEAR, 1, 2, "31".to_string());
+        let key = record_answer(&mut answers, 1, 2, "3   4\n", "42");
+        assert_eq!(key.input, input_hash("3   4"));
+        assert_eq!(answers.get(&key), Some("42"));
+        assert_eq!(answers.unpinned(&key), Some("31"));
+    }
+}
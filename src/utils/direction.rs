@@ -0,0 +1,257 @@
+use crate::utils::point::Point;
+use color_eyre::eyre::{eyre, Report, Result};
+use std::str::FromStr;
+use strum_macros::EnumIter;
+
+/// The four compass headings, using screen coordinates where north is `y - 1`.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Hash, EnumIter)]
+pub enum Cardinal {
+    North,
+    East,
+    South,
+    West,
+}
+
+/// All eight compass headings, using screen coordinates where north is `y - 1`.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Hash, EnumIter)]
+pub enum Ordinal {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Cardinal {
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        match self {
+            Cardinal::North => Cardinal::East,
+            Cardinal::East => Cardinal::South,
+            Cardinal::South => Cardinal::West,
+            Cardinal::West => Cardinal::North,
+        }
+    }
+
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        self.reverse().turn_right()
+    }
+
+    #[must_use]
+    pub fn reverse(self) -> Self {
+        self.turn_right().turn_right()
+    }
+
+    #[must_use]
+    pub fn to_offset(self) -> Point<i64> {
+        Ordinal::from(self).to_offset()
+    }
+
+    /// The arrow used for this heading in puzzle maps.
+    #[must_use]
+    pub fn glyph(self) -> char {
+        match self {
+            Cardinal::North => '^',
+            Cardinal::East => '>',
+            Cardinal::South => 'v',
+            Cardinal::West => '<',
+        }
+    }
+}
+
+impl Ordinal {
+    /// Rotates 90 degrees clockwise.
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        self.clockwise().clockwise()
+    }
+
+    /// Rotates 90 degrees counter-clockwise.
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        self.counter_clockwise().counter_clockwise()
+    }
+
+    /// Rotates 45 degrees clockwise.
+    #[must_use]
+    pub fn clockwise(self) -> Self {
+        match self {
+            Ordinal::North => Ordinal::NorthEast,
+            Ordinal::NorthEast => Ordinal::East,
+            Ordinal::East => Ordinal::SouthEast,
+            Ordinal::SouthEast => Ordinal::South,
+            Ordinal::South => Ordinal::SouthWest,
+            Ordinal::SouthWest => Ordinal::West,
+            Ordinal::West => Ordinal::NorthWest,
+            Ordinal::NorthWest => Ordinal::North,
+        }
+    }
+
+    /// Rotates 45 degrees counter-clockwise.
+    #[must_use]
+    pub fn counter_clockwise(self) -> Self {
+        self.reverse().clockwise().clockwise().clockwise()
+    }
+
+    #[must_use]
+    pub fn reverse(self) -> Self {
+        self.turn_right().turn_right()
+    }
+
+    #[must_use]
+    pub fn is_diagonal(self) -> bool {
+        matches!(
+            self,
+            Ordinal::NorthEast | Ordinal::SouthEast | Ordinal::SouthWest | Ordinal::NorthWest
+        )
+    }
+
+    #[must_use]
+    pub fn to_offset(self) -> Point<i64> {
+        match self {
+            Ordinal::North => Point::new(0, -1),
+            Ordinal::NorthEast => Point::new(1, -1),
+            Ordinal::East => Point::new(1, 0),
+            Ordinal::SouthEast => Point::new(1, 1),
+            Ordinal::South => Point::new(0, 1),
+            Ordinal::SouthWest => Point::new(-1, 1),
+            Ordinal::West => Point::new(-1, 0),
+            Ordinal::NorthWest => Point::new(-1, -1),
+        }
+    }
+}
+
+impl From<Cardinal> for Ordinal {
+    fn from(value: Cardinal) -> Self {
+        match value {
+            Cardinal::North => Ordinal::North,
+            Cardinal::East => Ordinal::East,
+            Cardinal::South => Ordinal::South,
+            Cardinal::West => Ordinal::West,
+        }
+    }
+}
+
+/// Accepts map arrows (`^>v<`), compass letters (`NESW`) and movement letters (`UDLR`).
+impl TryFrom<char> for Cardinal {
+    type Error = Report;
+
+    fn try_from(value: char) -> Result<Self> {
+        match value {
+            '^' | 'N' | 'U' => Ok(Cardinal::North),
+            '>' | 'E' | 'R' => Ok(Cardinal::East),
+            'v' | 'S' | 'D' => Ok(Cardinal::South),
+            '<' | 'W' | 'L' => Ok(Cardinal::West),
+            _ => Err(eyre!("{value:?} is not a cardinal direction")),
+        }
+    }
+}
+
+impl FromStr for Cardinal {
+    type Err = Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => c.try_into(),
+            _ => Err(eyre!("{s:?} is not a cardinal direction")),
+        }
+    }
+}
+
+/// Accepts anything [`Cardinal`] does, plus the two letter diagonals `NE`, `SE`, `SW` and `NW`.
+impl FromStr for Ordinal {
+    type Err = Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "NE" => Ok(Ordinal::NorthEast),
+            "SE" => Ok(Ordinal::SouthEast),
+            "SW" => Ok(Ordinal::SouthWest),
+            "NW" => Ok(Ordinal::NorthWest),
+            _ => s
+                .parse::<Cardinal>()
+                .map(Ordinal::from)
+                .map_err(|_| eyre!("{s:?} is not an ordinal direction")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn cardinal_rotation() {
+        for dir in Cardinal::iter() {
+            assert_eq!(dir.turn_right().turn_left(), dir);
+            assert_eq!(dir.reverse().reverse(), dir);
+            assert_eq!(dir.turn_right().turn_right(), dir.reverse());
+            assert_eq!(dir.reverse().to_offset(), -dir.to_offset());
+        }
+
+        assert_eq!(Cardinal::North.turn_right(), Cardinal::East);
+        assert_eq!(Cardinal::North.turn_left(), Cardinal::West);
+    }
+
+    #[test]
+    fn ordinal_rotation() {
+        for dir in Ordinal::iter() {
+            assert_eq!(dir.clockwise().counter_clockwise(), dir);
+            assert_eq!(dir.turn_right().turn_left(), dir);
+            assert_eq!(dir.reverse().to_offset(), -dir.to_offset());
+            assert_eq!(dir.turn_right().is_diagonal(), dir.is_diagonal());
+        }
+
+        assert_eq!(Ordinal::NorthEast.turn_right(), Ordinal::SouthEast);
+        assert_eq!(Ordinal::North.counter_clockwise(), Ordinal::NorthWest);
+        assert_eq!(Ordinal::iter().filter(|dir| dir.is_diagonal()).count(), 4);
+    }
+
+    #[test]
+    fn offsets() {
+        let unique: std::collections::HashSet<_> =
+            Ordinal::iter().map(Ordinal::to_offset).collect();
+        assert_eq!(unique.len(), 8);
+
+        assert_eq!(Cardinal::North.to_offset(), Point::new(0, -1));
+        assert_eq!(Ordinal::SouthWest.to_offset(), Point::new(-1, 1));
+
+        let walked: Point<i64> = Cardinal::iter().map(Cardinal::to_offset).sum();
+        assert_eq!(walked, Point::new(0, 0));
+    }
+
+    #[rstest]
+    #[case("^", Cardinal::North)]
+    #[case(">", Cardinal::East)]
+    #[case("v", Cardinal::South)]
+    #[case("<", Cardinal::West)]
+    #[case("N", Cardinal::North)]
+    #[case("E", Cardinal::East)]
+    #[case("S", Cardinal::South)]
+    #[case("W", Cardinal::West)]
+    #[case("U", Cardinal::North)]
+    #[case("R", Cardinal::East)]
+    #[case("D", Cardinal::South)]
+    #[case("L", Cardinal::West)]
+    fn cardinal_parsing(#[case] input: &str, #[case] expected: Cardinal) {
+        assert_eq!(input.parse::<Cardinal>().unwrap(), expected);
+        assert_eq!(input.parse::<Ordinal>().unwrap(), Ordinal::from(expected));
+    }
+
+    #[test]
+    fn ordinal_parsing() {
+        assert_eq!("NE".parse::<Ordinal>().unwrap(), Ordinal::NorthEast);
+        assert_eq!("NW".parse::<Ordinal>().unwrap(), Ordinal::NorthWest);
+        assert!("NE".parse::<Cardinal>().is_err());
+        assert!("x".parse::<Cardinal>().is_err());
+        assert!("".parse::<Ordinal>().is_err());
+    }
+}
@@ -0,0 +1,453 @@
+use crate::utils::{
+    direction::{Cardinal, Ordinal},
+    point::Point,
+};
+use color_eyre::eyre::{ensure, eyre, Report, Result};
+use itertools::Itertools;
+use std::{
+    fmt::{self, Debug, Display},
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+use strum::IntoEnumIterator;
+
+/// A rectangular grid of cells stored row-major in a single flat buffer.
+///
+/// Cells are addressed with `Point<usize>`, where `x` is the column and `y` is the row.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: vec![fill; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Builds a grid from a list of rows.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the rows are not all the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+
+        if let Some((y, row)) = rows.iter().find_position(|row| row.len() != width) {
+            return Err(eyre!(
+                "row {y} has {} cells but the first row has {width}",
+                row.len()
+            ));
+        }
+
+        Ok(Self {
+            cells: rows.into_iter().flatten().collect(),
+            width,
+            height,
+        })
+    }
+
+    /// Parses a grid with one cell per character, using `cell` to convert each character.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `cell` rejects a character, the rows are ragged, or the input is empty.
+    pub fn parse_with<F>(input: &str, mut cell: F) -> Result<Self>
+    where
+        F: FnMut(char) -> Result<T>,
+    {
+        let rows = input
+            .lines()
+            .map(|line| line.chars().map(&mut cell).collect::<Result<Vec<_>>>())
+            .collect::<Result<Vec<_>>>()?;
+
+        let grid = Self::from_rows(rows)?;
+        ensure!(!grid.cells.is_empty(), "grid is empty");
+
+        Ok(grid)
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    #[must_use]
+    pub fn in_bounds(&self, point: Point<usize>) -> bool {
+        point.x < self.width && point.y < self.height
+    }
+
+    fn offset(&self, point: Point<usize>) -> Option<usize> {
+        self.in_bounds(point)
+            .then(|| point.y * self.width + point.x)
+    }
+
+    fn point(&self, offset: usize) -> Point<usize> {
+        Point::new(offset % self.width, offset / self.width)
+    }
+
+    #[must_use]
+    pub fn get(&self, point: Point<usize>) -> Option<&T> {
+        self.offset(point).map(|offset| &self.cells[offset])
+    }
+
+    pub fn get_mut(&mut self, point: Point<usize>) -> Option<&mut T> {
+        self.offset(point).map(|offset| &mut self.cells[offset])
+    }
+
+    /// Looks up a cell by a signed position, returning `None` for anything off the grid.
+    #[must_use]
+    pub fn get_signed(&self, point: Point<i64>) -> Option<&T> {
+        let x = usize::try_from(point.x).ok()?;
+        let y = usize::try_from(point.y).ok()?;
+
+        self.get(Point::new(x, y))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point<usize>, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(offset, cell)| (self.point(offset), cell))
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point<usize>> {
+        let width = self.width;
+        (0..self.height)
+            .cartesian_product(0..width)
+            .map(|(y, x)| Point::new(x, y))
+    }
+
+    #[must_use]
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height).then(|| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a zero chunk size, which an empty grid would otherwise produce.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    #[must_use]
+    pub fn column(&self, x: usize) -> Option<impl Iterator<Item = &T>> {
+        (x < self.width).then(|| self.cells.iter().skip(x).step_by(self.width))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).filter_map(|x| self.column(x))
+    }
+
+    /// Returns the position of the first cell, in row-major order, matching `predicate`.
+    pub fn find<P>(&self, mut predicate: P) -> Option<Point<usize>>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.cells
+            .iter()
+            .position(&mut predicate)
+            .map(|offset| self.point(offset))
+    }
+
+    /// Returns the positions of every cell matching `predicate`, in row-major order.
+    pub fn positions<'a, P>(&'a self, predicate: P) -> impl Iterator<Item = Point<usize>> + 'a
+    where
+        P: FnMut(&T) -> bool + 'a,
+    {
+        self.cells
+            .iter()
+            .positions(predicate)
+            .map(|offset| self.point(offset))
+    }
+
+    /// Moves `point` by `offset`, returning `None` if that leaves the grid.
+    #[must_use]
+    pub fn step(&self, point: Point<usize>, offset: Point<i64>) -> Option<Point<usize>> {
+        let x = i64::try_from(point.x).ok()?.checked_add(offset.x)?;
+        let y = i64::try_from(point.y).ok()?.checked_add(offset.y)?;
+        let stepped = Point::new(usize::try_from(x).ok()?, usize::try_from(y).ok()?);
+
+        self.in_bounds(stepped).then_some(stepped)
+    }
+
+    /// The up to four orthogonally adjacent points that lie on the grid.
+    pub fn neighbors4(&self, point: Point<usize>) -> impl Iterator<Item = Point<usize>> + '_ {
+        Cardinal::iter().filter_map(move |dir| self.step(point, dir.to_offset()))
+    }
+
+    /// The up to eight orthogonally and diagonally adjacent points that lie on the grid.
+    pub fn neighbors8(&self, point: Point<usize>) -> impl Iterator<Item = Point<usize>> + '_ {
+        Ordinal::iter().filter_map(move |dir| self.step(point, dir.to_offset()))
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// A copy of the grid turned a quarter turn clockwise, so the left column becomes the top row.
+    #[must_use]
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .flat_map(|x| (0..self.height).rev().map(move |y| Point::new(x, y)))
+            .map(|point| self[point].clone())
+            .collect();
+
+        Grid {
+            cells,
+            width: self.height,
+            height: self.width,
+        }
+    }
+}
+
+impl<T> Index<Point<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, index: Point<usize>) -> &Self::Output {
+        self.get(index)
+            .unwrap_or_else(|| panic!("{index:?} is outside a {}x{} grid", self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Point<usize>> for Grid<T> {
+    fn index_mut(&mut self, index: Point<usize>) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(index)
+            .unwrap_or_else(|| panic!("{index:?} is outside a {width}x{height} grid"))
+    }
+}
+
+impl<T> FromStr for Grid<T>
+where
+    T: TryFrom<char>,
+{
+    type Err = Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse_with(s, |c| {
+            T::try_from(c).map_err(|_| eyre!("unexpected grid character {c:?}"))
+        })
+    }
+}
+
+/// Renders the grid back into the character map it was parsed from.
+impl<T> Display for Grid<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Formats as a list of rows so the output reads like the `Vec<Vec<T>>` it replaces.
+impl<T> Debug for Grid<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.rows()).finish()
+    }
+}
+
+pub mod parsers {
+    use super::Grid;
+    use nom::{
+        character::complete::newline,
+        combinator::map_opt,
+        multi::{many1, separated_list1},
+        Parser,
+    };
+    use nom_locate::LocatedSpan;
+    use nom_supreme::{error::ErrorTree, ParserExt};
+
+    type ParseError<'a> = ErrorTree<Span<'a>>;
+
+    type Span<'a> = LocatedSpan<&'a str>;
+
+    /// Parses newline separated rows of cells into a rectangular grid.
+    pub fn grid<'a, T, P>(cell: P) -> impl Parser<Span<'a>, Grid<T>, ParseError<'a>>
+    where
+        P: Parser<Span<'a>, T, ParseError<'a>>,
+    {
+        map_opt(
+            separated_list1(newline, many1(cell).context("grid row")),
+            |rows| Grid::from_rows(rows).ok(),
+        )
+        .context("rectangular grid")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use pretty_assertions::assert_eq;
+
+    const SAMPLE: &str = indoc! {
+        "ab#d
+         e#gh
+         ijk#"
+    };
+
+    fn sample() -> Grid<char> {
+        SAMPLE.parse().unwrap()
+    }
+
+    #[test]
+    fn parsing() {
+        let grid = sample();
+
+        assert_eq!(grid.width(), 4);
+        assert_eq!(grid.height(), 3);
+        assert_eq!(grid.get(Point::new(1, 2)), Some(&'j'));
+        assert_eq!(grid.get(Point::new(4, 0)), None);
+        assert_eq!(grid.get(Point::new(0, 3)), None);
+        assert_eq!(grid[Point::new(3, 2)], '#');
+    }
+
+    #[test]
+    fn ragged_rows() {
+        assert!("abc\nde".parse::<Grid<char>>().is_err());
+        assert!(Grid::from_rows(vec![vec![1, 2], vec![3]]).is_err());
+    }
+
+    #[test]
+    fn display_round_trip() {
+        assert_eq!(sample().to_string(), SAMPLE);
+    }
+
+    #[test]
+    fn mutation() {
+        let mut grid = sample();
+        *grid.get_mut(Point::new(0, 0)).unwrap() = 'z';
+        grid[Point::new(1, 0)] = 'y';
+
+        assert_eq!(grid.row(0), Some(&['z', 'y', '#', 'd'][..]));
+        assert!(grid.get_mut(Point::new(9, 9)).is_none());
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = sample();
+
+        let rows: Vec<String> = grid.rows().map(|row| row.iter().collect()).collect();
+        assert_eq!(rows, vec!["ab#d", "e#gh", "ijk#"]);
+
+        let columns: Vec<String> = grid.columns().map(Iterator::collect).collect();
+        assert_eq!(columns, vec!["aei", "b#j", "#gk", "dh#"]);
+
+        assert!(grid.row(3).is_none());
+        assert!(grid.column(4).is_none());
+    }
+
+    #[test]
+    fn rotation() {
+        let rotated = sample().rotate_clockwise();
+
+        assert_eq!(rotated.to_string(), "iea\nj#b\nkg#\n#hd");
+        assert_eq!(
+            rotated
+                .rotate_clockwise()
+                .rotate_clockwise()
+                .rotate_clockwise(),
+            sample()
+        );
+    }
+
+    #[test]
+    fn searching() {
+        let grid = sample();
+
+        assert_eq!(grid.find(|c| *c == '#'), Some(Point::new(2, 0)));
+        assert_eq!(grid.find(|c| *c == 'q'), None);
+        assert_eq!(
+            grid.positions(|c| *c == '#').collect::<Vec<_>>(),
+            vec![Point::new(2, 0), Point::new(1, 1), Point::new(3, 2)]
+        );
+    }
+
+    #[test]
+    fn stepping() {
+        let grid = sample();
+
+        assert_eq!(
+            grid.step(Point::new(1, 1), Point::new(2, 1)),
+            Some(Point::new(3, 2))
+        );
+        assert_eq!(
+            grid.step(Point::new(1, 1), Point::new(-1, -1)),
+            Some(Point::new(0, 0))
+        );
+        assert_eq!(grid.step(Point::new(1, 1), Point::new(-2, 0)), None);
+        assert_eq!(grid.step(Point::new(1, 1), Point::new(0, 2)), None);
+    }
+
+    #[test]
+    fn neighborhoods() {
+        let grid = sample();
+
+        assert_eq!(
+            grid.neighbors4(Point::new(0, 0)).collect::<Vec<_>>(),
+            vec![Point::new(1, 0), Point::new(0, 1)]
+        );
+        assert_eq!(grid.neighbors4(Point::new(1, 1)).count(), 4);
+        assert_eq!(
+            grid.neighbors8(Point::new(3, 2)).collect::<Vec<_>>(),
+            vec![Point::new(3, 1), Point::new(2, 2), Point::new(2, 1)]
+        );
+        assert_eq!(grid.neighbors8(Point::new(1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn nom_parser() {
+        use nom::character::complete::one_of;
+        use nom_locate::LocatedSpan;
+        use nom_supreme::{error::ErrorTree, final_parser::final_parser};
+
+        type Span<'a> = LocatedSpan<&'a str>;
+
+        fn parse<'a>(
+            cells: &'static str,
+            input: &'a str,
+        ) -> Result<Grid<char>, ErrorTree<Span<'a>>> {
+            final_parser(parsers::grid(one_of(cells)))(Span::new(input))
+        }
+
+        assert_eq!(parse("abcdefghijk#", SAMPLE).unwrap(), sample());
+        assert!(parse("ab", "ab\na").is_err());
+    }
+}
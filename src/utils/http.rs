@@ -0,0 +1,91 @@
+//! The little HTTP the tools need to talk to the Advent of Code site, behind a trait so tests can
+//! point them at a local server instead.
+//!
+//! Requests authenticate with the site's session cookie, read from `AOC_SESSION`. `AOC_BASE_URL`
+//! overrides the site's address.
+
+use color_eyre::eyre::{eyre, Result, WrapErr};
+use std::env;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/tmuro17/aoc_2024_rust";
+
+pub trait HttpClient {
+    /// Fetches `url` with `session` as the session cookie, returning the body.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the request does, or if the response is not a success.
+    fn get(&self, url: &str, session: &str) -> Result<String>;
+
+    /// Posts `form` to `url` with `session` as the session cookie, returning the body.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the request does, or if the response is not a success.
+    fn post(&self, url: &str, session: &str, form: &[(&str, &str)]) -> Result<String>;
+}
+
+/// The [`HttpClient`] used outside of tests.
+#[derive(Debug, Clone)]
+pub struct UreqClient {
+    agent: ureq::Agent,
+}
+
+impl Default for UreqClient {
+    fn default() -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+        }
+    }
+}
+
+impl UreqClient {
+    fn body(url: &str, response: Result<ureq::Response, ureq::Error>) -> Result<String> {
+        let response = response.map_err(|e| match e {
+            ureq::Error::Status(status, _) => eyre!("{url} responded with status {status}"),
+            ureq::Error::Transport(transport) => eyre!("could not reach {url}: {transport}"),
+        })?;
+
+        response
+            .into_string()
+            .wrap_err_with(|| format!("could not read the response from {url}"))
+    }
+}
+
+impl HttpClient for UreqClient {
+    fn get(&self, url: &str, session: &str) -> Result<String> {
+        let response = self
+            .agent
+            .get(url)
+            .set("Cookie", &format!("session={session}"))
+            .call();
+
+        Self::body(url, response)
+    }
+
+    fn post(&self, url: &str, session: &str, form: &[(&str, &str)]) -> Result<String> {
+        let response = self
+            .agent
+            .post(url)
+            .set("Cookie", &format!("session={session}"))
+            .send_form(form);
+
+        Self::body(url, response)
+    }
+}
+
+/// The session cookie from `AOC_SESSION`, if it is set to something.
+#[must_use]
+pub fn session_from_env() -> Option<String> {
+    env::var("AOC_SESSION")
+        .ok()
+        .map(|session| session.trim().to_string())
+        .filter(|session| !session.is_empty())
+}
+
+/// The site's address from `AOC_BASE_URL`, or the real one.
+#[must_use]
+pub fn base_url_from_env() -> String {
+    env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.to_string())
+}
@@ -0,0 +1,293 @@
+//! Puzzle inputs, read from a local cache and fetched from the Advent of Code site when missing.
+//!
+//! The cache lives in `input/` unless `AOC_INPUT_DIR` says otherwise, laid out as
+//! `<dir>/<year>/day<N>.txt` as `cargo aoc` expects, with a `day<N>.toml` beside each fetched
+//! input recording where and when it came from. Fetching needs the site's session cookie in
+//! `AOC_SESSION`.
+
+use crate::utils::http::{
+    base_url_from_env, session_from_env, HttpClient, UreqClient, DEFAULT_BASE_URL,
+};
+use color_eyre::eyre::{bail, Result, WrapErr};
+use serde::{Deserialize, Serialize};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+pub const YEAR: u32 = 2024;
+
+const DEFAULT_DIR: &str = "input";
+
+/// Where and when a cached input was fetched from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputMetadata {
+    pub year: u32,
+    pub day: u32,
+    pub url: String,
+    /// Seconds since the Unix epoch.
+    pub fetched_at: u64,
+    pub bytes: usize,
+}
+
+/// Resolves puzzle inputs from a cache directory, fetching and storing any that are missing.
+#[derive(Debug, Clone)]
+pub struct InputCache<C = UreqClient> {
+    dir: PathBuf,
+    year: u32,
+    base_url: String,
+    session: Option<String>,
+    client: C,
+}
+
+impl InputCache {
+    /// A cache configured from `AOC_INPUT_DIR`, `AOC_SESSION` and `AOC_BASE_URL`, falling back to
+    /// `input/`, no session and the real site.
+    #[must_use]
+    pub fn from_env() -> Self {
+        let dir = env::var_os("AOC_INPUT_DIR").map_or_else(|| DEFAULT_DIR.into(), PathBuf::from);
+
+        InputCache::new(dir, UreqClient::default())
+            .with_session(session_from_env())
+            .with_base_url(base_url_from_env())
+    }
+}
+
+impl<C: HttpClient> InputCache<C> {
+    pub fn new(dir: impl Into<PathBuf>, client: C) -> Self {
+        Self {
+            dir: dir.into(),
+            year: YEAR,
+            base_url: DEFAULT_BASE_URL.to_string(),
+            session: None,
+            client,
+        }
+    }
+
+    #[must_use]
+    pub fn with_session(mut self, session: Option<String>) -> Self {
+        self.session = session;
+        self
+    }
+
+    #[must_use]
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    #[must_use]
+    pub fn path(&self, day: u32) -> PathBuf {
+        input_path(&self.dir, self.year, day)
+    }
+
+    #[must_use]
+    pub fn metadata_path(&self, day: u32) -> PathBuf {
+        self.path(day).with_extension("toml")
+    }
+
+    #[must_use]
+    pub fn url(&self, day: u32) -> String {
+        format!("{}/{}/day/{day}/input", self.base_url, self.year)
+    }
+
+    /// The day's input, from the cache if it is there and fetched into it otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the input is not cached and either there is no session to fetch it with, the
+    /// fetch fails, or it cannot be written to the cache.
+    pub fn load(&self, day: u32) -> Result<String> {
+        let path = self.path(day);
+        if path.exists() {
+            return read(&path);
+        }
+
+        let Some(session) = &self.session else {
+            bail!(
+                "no input for day {day}: {} does not exist and AOC_SESSION is not set to fetch it",
+                path.display()
+            );
+        };
+
+        let url = self.url(day);
+        let input = self
+            .client
+            .get(&url, session)
+            .wrap_err_with(|| format!("could not fetch the input for day {day}"))?;
+        self.store(day, &url, &input)?;
+
+        Ok(input)
+    }
+
+    /// The metadata recorded when the day's input was fetched, if it was.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the metadata file exists but cannot be read or parsed.
+    pub fn metadata(&self, day: u32) -> Result<Option<InputMetadata>> {
+        let path = self.metadata_path(day);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let metadata = toml::from_str(&read(&path)?)
+            .wrap_err_with(|| format!("{} is not valid input metadata", path.display()))?;
+        Ok(Some(metadata))
+    }
+
+    fn store(&self, day: u32, url: &str, input: &str) -> Result<()> {
+        let path = self.path(day);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .wrap_err_with(|| format!("could not create {}", parent.display()))?;
+        }
+
+        let metadata = InputMetadata {
+            year: self.year,
+            day,
+            url: url.to_string(),
+            fetched_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_secs()),
+            bytes: input.len(),
+        };
+
+        fs::write(&path, input).wrap_err_with(|| format!("could not write {}", path.display()))?;
+        let metadata_path = self.metadata_path(day);
+        fs::write(&metadata_path, toml::to_string(&metadata)?)
+            .wrap_err_with(|| format!("could not write {}", metadata_path.display()))
+    }
+}
+
+/// Where the input for `year` and `day` lives under the cache directory `dir`.
+#[must_use]
+pub fn input_path(dir: &Path, year: u32, day: u32) -> PathBuf {
+    dir.join(year.to_string()).join(format!("day{day}.txt"))
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path).wrap_err_with(|| format!("could not read {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        thread,
+    };
+    use tiny_http::{Header, Response, Server};
+
+    /// A local stand in for the site, serving `day/1/input` to the `abc` session only and
+    /// counting the requests it gets.
+    fn stub_server() -> (String, Arc<AtomicUsize>) {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", server.server_addr().to_ip().unwrap());
+        let requests = Arc::new(AtomicUsize::new(0));
+
+        let counter = Arc::clone(&requests);
+        thread::spawn(move || {
+            for request in server.incoming_requests() {
+                counter.fetch_add(1, Ordering::SeqCst);
+
+                let cookie = request
+                    .headers()
+                    .iter()
+                    .find(|h| h.field.equiv("Cookie"))
+                    .map(|h| h.value.to_string());
+
+                let response = match (request.url(), cookie.as_deref()) {
+                    ("/2024/day/1/input", Some("session=abc")) => {
+                        Response::from_string("3   4\n4   3\n")
+                    }
+                    (_, Some("session=abc")) => {
+                        Response::from_string("Not Found").with_status_code(404)
+                    }
+                    _ => {
+                        Response::from_string("Puzzle inputs differ by user.").with_status_code(400)
+                    }
+                }
+                .with_header(Header::from_bytes("Content-Type", "text/plain").unwrap());
+
+                let _ = request.respond(response);
+            }
+        });
+
+        (base_url, requests)
+    }
+
+    fn cache(dir: &Path, base_url: &str, session: Option<&str>) -> InputCache {
+        InputCache::new(dir, UreqClient::default())
+            .with_base_url(base_url)
+            .with_session(session.map(str::to_string))
+    }
+
+    #[test]
+    fn fetches_once_then_reads_the_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let (base_url, requests) = stub_server();
+        let cache = cache(dir.path(), &base_url, Some("abc"));
+
+        assert_eq!(cache.load(1).unwrap(), "3   4\n4   3\n");
+        assert_eq!(cache.load(1).unwrap(), "3   4\n4   3\n");
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+        assert_eq!(
+            fs::read_to_string(dir.path().join("2024/day1.txt")).unwrap(),
+            "3   4\n4   3\n"
+        );
+
+        let metadata = cache.metadata(1).unwrap().unwrap();
+        assert_eq!(metadata.url, format!("{base_url}/2024/day/1/input"));
+        assert_eq!((metadata.year, metadata.day, metadata.bytes), (2024, 1, 12));
+        assert!(metadata.fetched_at > 0);
+    }
+
+    #[test]
+    fn reads_existing_files_without_a_session() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("2024")).unwrap();
+        fs::write(dir.path().join("2024/day5.txt"), "cached").unwrap();
+
+        let cache = cache(dir.path(), "http://127.0.0.1:9", None);
+        assert_eq!(cache.load(5).unwrap(), "cached");
+        assert_eq!(cache.metadata(5).unwrap(), None);
+    }
+
+    #[test]
+    fn explains_missing_inputs() {
+        let dir = tempfile::tempdir().unwrap();
+        let (base_url, requests) = stub_server();
+
+        let error = cache(dir.path(), &base_url, None).load(1).unwrap_err();
+        assert!(
+            error.to_string().contains("AOC_SESSION is not set"),
+            "{error}"
+        );
+
+        let error = cache(dir.path(), &base_url, Some("wrong"))
+            .load(1)
+            .unwrap_err();
+        assert!(format!("{error:#}").contains("status 400"), "{error:#}");
+
+        let error = cache(dir.path(), &base_url, Some("abc"))
+            .load(2)
+            .unwrap_err();
+        assert!(format!("{error:#}").contains("status 404"), "{error:#}");
+
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+        assert!(!dir.path().join("2024/day1.txt").exists());
+        assert!(!dir.path().join("2024/day2.txt").exists());
+    }
+}
@@ -1 +1,6 @@
+pub mod direction;
+pub mod grid;
+pub mod http;
+pub mod input;
 pub mod point;
+pub mod toposort;
@@ -1,12 +1,16 @@
 use itertools::Itertools;
 use num::{
-    traits::{SaturatingAdd, SaturatingSub},
+    traits::{
+        CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, SaturatingAdd, SaturatingMul,
+        SaturatingSub, WrappingAdd, WrappingMul, WrappingSub,
+    },
     Float,
 };
 use std::{
     fmt::Debug,
     hash::{Hash, Hasher},
-    ops::Add,
+    iter::Sum,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign},
 };
 
 #[derive(Copy, Clone, Debug)]
@@ -74,6 +78,98 @@ where
         (max_x - min_x) + (max_y - min_y)
     }
 
+    /// Adds component-wise, returning `None` if either component overflows.
+    pub fn checked_add(&self, rhs: &Point<T>) -> Option<Self>
+    where
+        T: CheckedAdd,
+    {
+        Some(Self::new(
+            self.x.checked_add(&rhs.x)?,
+            self.y.checked_add(&rhs.y)?,
+        ))
+    }
+
+    /// Subtracts component-wise, returning `None` if either component overflows.
+    pub fn checked_sub(&self, rhs: &Point<T>) -> Option<Self>
+    where
+        T: CheckedSub,
+    {
+        Some(Self::new(
+            self.x.checked_sub(&rhs.x)?,
+            self.y.checked_sub(&rhs.y)?,
+        ))
+    }
+
+    /// Scales both components, returning `None` if either overflows.
+    pub fn checked_mul(&self, scale: T) -> Option<Self>
+    where
+        T: CheckedMul,
+    {
+        Some(Self::new(
+            self.x.checked_mul(&scale)?,
+            self.y.checked_mul(&scale)?,
+        ))
+    }
+
+    /// Divides both components, returning `None` on division by zero or overflow.
+    pub fn checked_div(&self, divisor: T) -> Option<Self>
+    where
+        T: CheckedDiv,
+    {
+        Some(Self::new(
+            self.x.checked_div(&divisor)?,
+            self.y.checked_div(&divisor)?,
+        ))
+    }
+
+    #[must_use]
+    pub fn saturating_add(&self, rhs: &Point<T>) -> Self
+    where
+        T: SaturatingAdd,
+    {
+        Self::new(self.x.saturating_add(&rhs.x), self.y.saturating_add(&rhs.y))
+    }
+
+    #[must_use]
+    pub fn saturating_sub(&self, rhs: &Point<T>) -> Self
+    where
+        T: SaturatingSub,
+    {
+        Self::new(self.x.saturating_sub(&rhs.x), self.y.saturating_sub(&rhs.y))
+    }
+
+    #[must_use]
+    pub fn saturating_mul(&self, scale: T) -> Self
+    where
+        T: SaturatingMul,
+    {
+        Self::new(self.x.saturating_mul(&scale), self.y.saturating_mul(&scale))
+    }
+
+    #[must_use]
+    pub fn wrapping_add(&self, rhs: &Point<T>) -> Self
+    where
+        T: WrappingAdd,
+    {
+        Self::new(self.x.wrapping_add(&rhs.x), self.y.wrapping_add(&rhs.y))
+    }
+
+    #[must_use]
+    pub fn wrapping_sub(&self, rhs: &Point<T>) -> Self
+    where
+        T: WrappingSub,
+    {
+        Self::new(self.x.wrapping_sub(&rhs.x), self.y.wrapping_sub(&rhs.y))
+    }
+
+    #[must_use]
+    pub fn wrapping_mul(&self, scale: T) -> Self
+    where
+        T: WrappingMul,
+    {
+        Self::new(self.x.wrapping_mul(&scale), self.y.wrapping_mul(&scale))
+    }
+
     #[allow(clippy::too_many_lines)]
     pub fn neighbors(&self) -> Vec<Self>
     where
@@ -228,6 +324,139 @@ where
     }
 }
 
+impl<T> Sub<Point<T>> for Point<T>
+where
+    T: Clone + Copy + Debug + num::Num,
+{
+    type Output = Point<T>;
+
+    fn sub(self, rhs: Point<T>) -> Self::Output {
+        Self {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+        }
+    }
+}
+
+impl<T> Neg for Point<T>
+where
+    T: Clone + Copy + Debug + num::Num + Neg<Output = T>,
+{
+    type Output = Point<T>;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl<T> Mul<T> for Point<T>
+where
+    T: Clone + Copy + Debug + num::Num,
+{
+    type Output = Point<T>;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Self {
+            x: self.x * rhs,
+            y: self.y * rhs,
+        }
+    }
+}
+
+impl<T> Div<T> for Point<T>
+where
+    T: Clone + Copy + Debug + num::Num,
+{
+    type Output = Point<T>;
+
+    fn div(self, rhs: T) -> Self::Output {
+        Self {
+            x: self.x / rhs,
+            y: self.y / rhs,
+        }
+    }
+}
+
+impl<T> Rem<T> for Point<T>
+where
+    T: Clone + Copy + Debug + num::Num,
+{
+    type Output = Point<T>;
+
+    fn rem(self, rhs: T) -> Self::Output {
+        Self {
+            x: self.x % rhs,
+            y: self.y % rhs,
+        }
+    }
+}
+
+impl<T> AddAssign<Point<T>> for Point<T>
+where
+    T: Clone + Copy + Debug + num::Num,
+{
+    fn add_assign(&mut self, rhs: Point<T>) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T> SubAssign<Point<T>> for Point<T>
+where
+    T: Clone + Copy + Debug + num::Num,
+{
+    fn sub_assign(&mut self, rhs: Point<T>) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T> MulAssign<T> for Point<T>
+where
+    T: Clone + Copy + Debug + num::Num,
+{
+    fn mul_assign(&mut self, rhs: T) {
+        *self = *self * rhs;
+    }
+}
+
+impl<T> DivAssign<T> for Point<T>
+where
+    T: Clone + Copy + Debug + num::Num,
+{
+    fn div_assign(&mut self, rhs: T) {
+        *self = *self / rhs;
+    }
+}
+
+impl<T> RemAssign<T> for Point<T>
+where
+    T: Clone + Copy + Debug + num::Num,
+{
+    fn rem_assign(&mut self, rhs: T) {
+        *self = *self % rhs;
+    }
+}
+
+impl<T> Sum for Point<T>
+where
+    T: Clone + Copy + Debug + num::Num,
+{
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::new(T::zero(), T::zero()), Add::add)
+    }
+}
+
+impl<'a, T> Sum<&'a Point<T>> for Point<T>
+where
+    T: Clone + Copy + Debug + num::Num,
+{
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
 impl<T> From<(T, T)> for Point<T>
 where
     T: Clone + Copy + Debug + num::Num,
@@ -331,4 +560,79 @@ mod tests {
         assert_eq!(neighbors.len(), 8);
         assert_debug_snapshot!(neighbors);
     }
+
+    #[test]
+    fn arithmetic() {
+        let a = Point::new(7, -3);
+        let b = Point::new(2, 5);
+
+        assert_eq!(a + b, Point::new(9, 2));
+        assert_eq!(a - b, Point::new(5, -8));
+        assert_eq!(-a, Point::new(-7, 3));
+        assert_eq!(a * 3, Point::new(21, -9));
+        assert_eq!(a / 2, Point::new(3, -1));
+        assert_eq!(a % 4, Point::new(3, -3));
+
+        let pos = Point::new(4u64, 6);
+        let dir = Point::new(1u64, 0);
+        assert_eq!(pos + dir * 5, Point::new(9, 6));
+
+        let a = Point::new(1.5, -0.5);
+        assert_eq!(a * 2.0, Point::new(3.0, -1.0));
+        assert_eq!(-a, Point::new(-1.5, 0.5));
+    }
+
+    #[test]
+    fn assignment() {
+        let mut pt = Point::new(10, 20);
+
+        pt += Point::new(1, 2);
+        assert_eq!(pt, Point::new(11, 22));
+
+        pt -= Point::new(3, 4);
+        assert_eq!(pt, Point::new(8, 18));
+
+        pt *= 2;
+        assert_eq!(pt, Point::new(16, 36));
+
+        pt /= 4;
+        assert_eq!(pt, Point::new(4, 9));
+
+        pt %= 3;
+        assert_eq!(pt, Point::new(1, 0));
+    }
+
+    #[test]
+    fn sums() {
+        let steps = vec![Point::new(1, 0), Point::new(0, -1), Point::new(-3, 2)];
+
+        assert_eq!(steps.iter().sum::<Point<i32>>(), Point::new(-2, 1));
+        assert_eq!(steps.into_iter().sum::<Point<i32>>(), Point::new(-2, 1));
+        assert_eq!(
+            std::iter::empty::<Point<u8>>().sum::<Point<u8>>(),
+            Point::new(0, 0)
+        );
+    }
+
+    #[test]
+    fn overflow_handling() {
+        let pt = Point::new(250u8, 3);
+
+        assert_eq!(pt.checked_add(&Point::new(5, 5)), Some(Point::new(255, 8)));
+        assert_eq!(pt.checked_add(&Point::new(6, 0)), None);
+        assert_eq!(pt.checked_sub(&Point::new(0, 4)), None);
+        assert_eq!(pt.checked_sub(&Point::new(50, 3)), Some(Point::new(200, 0)));
+        assert_eq!(pt.checked_mul(2), None);
+        assert_eq!(Point::new(4u8, 3).checked_mul(2), Some(Point::new(8, 6)));
+        assert_eq!(pt.checked_div(0), None);
+        assert_eq!(pt.checked_div(2), Some(Point::new(125, 1)));
+
+        assert_eq!(pt.saturating_add(&Point::new(10, 1)), Point::new(255, 4));
+        assert_eq!(pt.saturating_sub(&Point::new(1, 10)), Point::new(249, 0));
+        assert_eq!(pt.saturating_mul(2), Point::new(255, 6));
+
+        assert_eq!(pt.wrapping_add(&Point::new(10, 1)), Point::new(4, 4));
+        assert_eq!(pt.wrapping_sub(&Point::new(1, 10)), Point::new(249, 249));
+        assert_eq!(pt.wrapping_mul(2), Point::new(244, 6));
+    }
 }
@@ -0,0 +1,166 @@
+use itertools::Itertools;
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    error::Error,
+    fmt::{self, Debug, Display},
+    hash::Hash,
+};
+
+/// An ordering of nodes consistent with every edge between them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sorted<T> {
+    pub order: Vec<T>,
+    /// Whether `order` is the only ordering the edges allow.
+    pub unique: bool,
+}
+
+/// The nodes of a cycle, in edge order, which prevents any ordering from existing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<T>(pub Vec<T>);
+
+impl<T> Display for Cycle<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cycle {}", self.0.iter().join(" -> "))?;
+        if let Some(first) = self.0.first() {
+            write!(f, " -> {first}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> Error for Cycle<T> where T: Debug + Display {}
+
+/// Orders `nodes` so that for every `(before, after)` edge, `before` comes first, using Kahn's
+/// algorithm.
+///
+/// Edges that mention a node outside of `nodes` are ignored, so a global rule set can be applied
+/// to any subset of it. Where the edges leave a choice, the earliest node in the input that is ready
+/// goes next, so nodes keep their relative input order unless an edge moves them.
+///
+/// # Errors
+///
+/// Returns the nodes of a [`Cycle`] if the edges between `nodes` contain one.
+pub fn toposort<T, I>(nodes: &[T], edges: I) -> Result<Sorted<T>, Cycle<T>>
+where
+    T: Copy + Eq + Hash,
+    I: IntoIterator<Item = (T, T)>,
+{
+    let nodes: Vec<T> = nodes.iter().copied().unique().collect();
+    let index: HashMap<T, usize> = nodes.iter().enumerate().map(|(i, n)| (*n, i)).collect();
+
+    let mut successors = vec![Vec::new(); nodes.len()];
+    let mut predecessors = vec![Vec::new(); nodes.len()];
+    for (before, after) in edges {
+        if let (Some(&before), Some(&after)) = (index.get(&before), index.get(&after)) {
+            successors[before].push(after);
+            predecessors[after].push(before);
+        }
+    }
+
+    let mut in_degree: Vec<usize> = predecessors.iter().map(Vec::len).collect();
+    // A min-heap on input position, so ties always go to the earliest node.
+    let mut ready: BinaryHeap<Reverse<usize>> = (0..nodes.len())
+        .filter(|i| in_degree[*i] == 0)
+        .map(Reverse)
+        .collect();
+    let mut order = Vec::with_capacity(nodes.len());
+    let mut unique = true;
+
+    while let Some(Reverse(next)) = ready.pop() {
+        unique &= ready.is_empty();
+        order.push(next);
+
+        for &after in &successors[next] {
+            in_degree[after] -= 1;
+            if in_degree[after] == 0 {
+                ready.push(Reverse(after));
+            }
+        }
+    }
+
+    if order.len() < nodes.len() {
+        let cycle = find_cycle(&predecessors, &in_degree);
+        return Err(Cycle(cycle.into_iter().map(|i| nodes[i]).collect()));
+    }
+
+    Ok(Sorted {
+        order: order.into_iter().map(|i| nodes[i]).collect(),
+        unique,
+    })
+}
+
+/// Every node Kahn's algorithm could not place still has a predecessor it could not place, so
+/// walking backwards through those must eventually repeat a node.
+fn find_cycle(predecessors: &[Vec<usize>], in_degree: &[usize]) -> Vec<usize> {
+    let stuck = |i: usize| in_degree[i] > 0;
+
+    let mut current = (0..in_degree.len()).find(|i| stuck(*i)).unwrap();
+    let mut walked = vec![];
+    let mut position = HashMap::new();
+
+    while !position.contains_key(&current) {
+        position.insert(current, walked.len());
+        walked.push(current);
+        current = *predecessors[current].iter().find(|p| stuck(**p)).unwrap();
+    }
+
+    let mut cycle = walked.split_off(position[&current]);
+    cycle.reverse();
+
+    // Start from the earliest node so the reported cycle does not depend on where the walk began.
+    let earliest = cycle.iter().position_min().unwrap();
+    cycle.rotate_left(earliest);
+    cycle
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn chain() {
+        let sorted = toposort(&[3, 1, 2], [(1, 2), (2, 3)]).unwrap();
+
+        assert_eq!(sorted.order, vec![1, 2, 3]);
+        assert!(sorted.unique);
+    }
+
+    #[test]
+    fn ambiguous() {
+        let sorted = toposort(&[4, 1, 2, 3], [(1, 2), (1, 3)]).unwrap();
+
+        assert_eq!(sorted.order, vec![4, 1, 2, 3]);
+        assert!(!sorted.unique);
+    }
+
+    #[test]
+    fn restricted_to_nodes() {
+        let edges = [(1, 2), (2, 3), (3, 1), (5, 4)];
+
+        let sorted = toposort(&[4, 5], edges).unwrap();
+        assert_eq!(sorted.order, vec![5, 4]);
+        assert!(sorted.unique);
+
+        let sorted = toposort(&[3, 2, 6], edges).unwrap();
+        assert_eq!(sorted.order, vec![2, 3, 6]);
+        assert!(!sorted.unique);
+    }
+
+    #[test]
+    fn cycles() {
+        let cycle =
+            toposort(&[1, 2, 3, 4, 5], [(5, 1), (1, 2), (2, 3), (3, 4), (4, 2)]).unwrap_err();
+
+        assert_eq!(cycle, Cycle(vec![2, 3, 4]));
+        assert_eq!(cycle.to_string(), "cycle 2 -> 3 -> 4 -> 2");
+
+        let cycle = toposort(&["a", "b"], [("a", "b"), ("b", "a")]).unwrap_err();
+        assert_eq!(cycle.0.len(), 2);
+    }
+}